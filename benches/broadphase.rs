@@ -0,0 +1,95 @@
+//! Compares testing every ball against every collider with testing it only against the
+//! `ColliderGrid` cells along its path, for growing numbers of small bricks.
+//!
+//! Run with `cargo bench --bench broadphase`.
+
+use std::{hint::black_box, time::{Duration, Instant}};
+
+use bevy::{math::bounding::{Aabb2d, BoundingCircle}, prelude::*};
+use kuzusi::{collisions::swept_ball_collision, prelude::{swept_area, ColliderGrid}};
+
+const BRICK_SIZE: Vec2 = Vec2::new(20.0, 10.0);
+const BRICK_GAP: f32 = 2.0;
+const BALL_RADIUS: f32 = 15.0;
+const BALLS: usize = 8;
+// one physics tick at 120 Hz and 600 units per second
+const BALL_MOTION: f32 = 5.0;
+const CELL_SIZE: f32 = 64.0;
+const TICKS: usize = 200;
+
+fn main() {
+    println!("{:>8} {:>14} {:>14} {:>9}", "bricks", "brute force", "grid", "speedup");
+    for side in [10, 32, 100, 316] {
+        let bricks = brick_field(side);
+        let balls = balls_over(&bricks);
+
+        let brute_force = time_ticks(|| {
+            let mut hits = 0;
+            for (center, motion) in &balls {
+                for brick in &bricks {
+                    hits += sweep(*center, *motion, *brick);
+                }
+            }
+            hits
+        });
+
+        let mut grid = ColliderGrid::new(CELL_SIZE);
+        for (index, brick) in bricks.iter().enumerate() {
+            grid.insert(Entity::from_raw_u32(index as u32).unwrap(), *brick);
+        }
+        let grid_time = time_ticks(|| {
+            let mut hits = 0;
+            for (center, motion) in &balls {
+                for entity in grid.query(swept_area(*center, BALL_RADIUS, *motion)) {
+                    hits += sweep(*center, *motion, bricks[entity.index() as usize]);
+                }
+            }
+            hits
+        });
+
+        println!(
+            "{:>8} {:>14?} {:>14?} {:>8.1}x",
+            bricks.len(),
+            brute_force,
+            grid_time,
+            brute_force.as_secs_f64() / grid_time.as_secs_f64(),
+        );
+    }
+}
+
+// `side` by `side` bricks packed next to each other
+fn brick_field(side: usize) -> Vec<Aabb2d> {
+    let pitch = BRICK_SIZE + Vec2::splat(BRICK_GAP);
+    (0..side * side)
+        .map(|index| {
+            let cell = Vec2::new((index % side) as f32, (index / side) as f32);
+            Aabb2d::new(cell * pitch, BRICK_SIZE / 2.0)
+        })
+        .collect()
+}
+
+// balls spread over the field, moving in different directions
+fn balls_over(bricks: &[Aabb2d]) -> Vec<(Vec2, Vec2)> {
+    let far_corner = bricks.last().map_or(Vec2::ZERO, |brick| brick.max);
+    (0..BALLS)
+        .map(|index| {
+            let t = (index as f32 + 0.5) / BALLS as f32;
+            let angle = t * std::f32::consts::TAU;
+            (far_corner * Vec2::new(t, 1.0 - t), Vec2::from_angle(angle) * BALL_MOTION)
+        })
+        .collect()
+}
+
+fn sweep(center: Vec2, motion: Vec2, brick: Aabb2d) -> usize {
+    let ball = BoundingCircle::new(center, BALL_RADIUS);
+    usize::from(swept_ball_collision(black_box(ball), black_box(motion), brick, false).is_some())
+}
+
+// mean time of one tick over `TICKS` ticks
+fn time_ticks(mut tick: impl FnMut() -> usize) -> Duration {
+    let start = Instant::now();
+    for _ in 0..TICKS {
+        black_box(tick());
+    }
+    start.elapsed() / TICKS as u32
+}
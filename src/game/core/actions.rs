@@ -0,0 +1,263 @@
+use std::{collections::{BTreeMap, HashMap, HashSet}, path::{Path, PathBuf}};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::prelude::*;
+
+// What the player wants to do, independent of the button that asks for it.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Serve,
+    Pause,
+    Back,
+    Confirm,
+    MenuUp,
+    MenuDown,
+    // opens the controls screen from the start menu
+    Controls,
+    // plays back the last stage recorded, from the start menu
+    WatchReplay,
+    // shows or hides the collider debug overlay
+    DebugOverlay,
+}
+
+impl Action {
+    pub const ALL: [Action; 11] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Serve,
+        Action::Pause,
+        Action::Back,
+        Action::Confirm,
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::Controls,
+        Action::WatchReplay,
+        Action::DebugOverlay,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move Left",
+            Action::MoveRight => "Move Right",
+            Action::Serve => "Serve",
+            Action::Pause => "Pause",
+            Action::Back => "Back",
+            Action::Confirm => "Confirm",
+            Action::MenuUp => "Menu Up",
+            Action::MenuDown => "Menu Down",
+            Action::Controls => "Controls",
+            Action::WatchReplay => "Watch Replay",
+            Action::DebugOverlay => "Debug Overlay",
+        }
+    }
+
+    fn default_bindings(&self) -> Vec<Binding> {
+        use Binding::{Axis, Gamepad, Key, Mouse};
+        match self {
+            Action::MoveLeft => vec![
+                Key(KeyCode::ArrowLeft),
+                Gamepad(GamepadButton::DPadLeft),
+                Axis { axis: GamepadAxis::LeftStickX, positive: false },
+            ],
+            Action::MoveRight => vec![
+                Key(KeyCode::ArrowRight),
+                Gamepad(GamepadButton::DPadRight),
+                Axis { axis: GamepadAxis::LeftStickX, positive: true },
+            ],
+            Action::Serve => vec![Key(KeyCode::Space), Mouse(MouseButton::Left), Gamepad(GamepadButton::South)],
+            Action::Pause => vec![Key(KeyCode::KeyP), Key(KeyCode::Escape), Gamepad(GamepadButton::Start)],
+            Action::Back => vec![Key(KeyCode::Escape), Key(KeyCode::Backspace), Gamepad(GamepadButton::East)],
+            Action::Confirm => vec![Key(KeyCode::Space), Key(KeyCode::Enter), Gamepad(GamepadButton::South)],
+            Action::MenuUp => vec![Key(KeyCode::ArrowUp), Gamepad(GamepadButton::DPadUp)],
+            Action::MenuDown => vec![Key(KeyCode::ArrowDown), Gamepad(GamepadButton::DPadDown)],
+            Action::Controls => vec![Key(KeyCode::KeyC), Gamepad(GamepadButton::Select)],
+            Action::WatchReplay => vec![Key(KeyCode::KeyR), Gamepad(GamepadButton::North)],
+            Action::DebugOverlay => vec![Key(KeyCode::F3)],
+        }
+    }
+}
+
+// One physical input. Gamepad inputs are read from every connected gamepad.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+    // one half of a stick axis, held in proportion to its deflection; never just pressed
+    Axis { axis: GamepadAxis, positive: bool },
+}
+
+impl Binding {
+    // keys, mouse buttons and gamepad buttons are each rebound separately, stick axes never
+    fn same_device(&self, other: &Binding) -> bool {
+        matches!(
+            (self, other),
+            (Binding::Key(_), Binding::Key(_))
+                | (Binding::Mouse(_), Binding::Mouse(_))
+                | (Binding::Gamepad(_), Binding::Gamepad(_))
+        )
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Binding::Key(key) => format!("{key:?}"),
+            Binding::Mouse(button) => format!("Mouse {button:?}"),
+            Binding::Gamepad(button) => format!("Pad {button:?}"),
+            Binding::Axis { axis, positive } => format!("Pad {axis:?}{}", if *positive { "+" } else { "-" }),
+        }
+    }
+}
+
+// Which inputs trigger each action. Several inputs may share an action and one input
+// may trigger several actions (Escape pauses in play and goes back in menus).
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InputBindings {
+    bindings: BTreeMap<Action, Vec<Binding>>,
+}
+
+// Where the bindings are read from and written to.
+#[derive(Resource, Deref)]
+pub struct InputBindingsFile(pub PathBuf);
+
+impl Default for InputBindingsFile {
+    fn default() -> Self {
+        Self(config_path(BINDINGS_FILE))
+    }
+}
+
+// A file that cannot be read leaves the default bindings in place.
+impl FromWorld for InputBindings {
+    fn from_world(world: &mut World) -> Self {
+        let path = &world.resource::<InputBindingsFile>().0;
+        InputBindings::load(path).unwrap_or_else(|err| {
+            warn!("Using default controls instead of {}: {err}", path.display());
+            InputBindings::defaults()
+        })
+    }
+}
+
+impl InputBindings {
+    // every action on its built-in inputs
+    pub fn defaults() -> Self {
+        Self { bindings: Action::ALL.iter().map(|action| (*action, action.default_bindings())).collect() }
+    }
+
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    // names the action's first input in on-screen prompts, so they follow rebinding
+    pub fn first_label(&self, action: Action) -> String {
+        self.get(action).first().map_or(String::from("?"), Binding::label)
+    }
+
+    // Replaces the action's inputs from the same device as `binding`, keeping the others.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|existing| !existing.same_device(&binding));
+        bindings.push(binding);
+    }
+
+    // A missing file means the defaults, and actions the file leaves out keep theirs.
+    pub fn load(path: &Path) -> Result<Self, RonFileError> {
+        let Some(mut bindings) = load_ron::<InputBindings>(path)? else { return Ok(Self::defaults()); };
+        for action in Action::ALL {
+            bindings.bindings.entry(action).or_insert_with(|| action.default_bindings());
+        }
+        Ok(bindings)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), RonFileError> {
+        save_ron(path, self)
+    }
+}
+
+// The actions held and pressed this frame, rebuilt from `InputBindings` in `PreUpdate`.
+#[derive(Resource, Default, Debug)]
+pub struct ActionState {
+    values: HashMap<Action, f32>,
+    just_pressed: HashSet<Action>,
+}
+
+impl ActionState {
+    // how far the action is held, from 0 to 1; only stick axes give values in between
+    pub fn value(&self, action: Action) -> f32 {
+        self.values.get(&action).copied().unwrap_or(0.0)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    // so a press handled by one system is not seen again by another in the same frame
+    pub fn clear_just_pressed(&mut self, action: Action) {
+        self.just_pressed.remove(&action);
+    }
+
+    pub fn update(
+        &mut self,
+        bindings: &InputBindings,
+        keys: &ButtonInput<KeyCode>,
+        mouse_buttons: &ButtonInput<MouseButton>,
+        gamepads: &Query<&Gamepad>,
+    ) {
+        self.values.clear();
+        self.just_pressed.clear();
+
+        for action in Action::ALL {
+            let mut value: f32 = 0.0;
+            let mut just_pressed = false;
+            for binding in bindings.get(action) {
+                let (binding_value, binding_just_pressed) = match binding {
+                    Binding::Key(key) => (held(keys.pressed(*key)), keys.just_pressed(*key)),
+                    Binding::Mouse(button) => (held(mouse_buttons.pressed(*button)), mouse_buttons.just_pressed(*button)),
+                    Binding::Gamepad(button) => (
+                        held(gamepads.iter().any(|gamepad| gamepad.pressed(*button))),
+                        gamepads.iter().any(|gamepad| gamepad.just_pressed(*button)),
+                    ),
+                    Binding::Axis { axis, positive } => {
+                        let sign = if *positive { 1.0 } else { -1.0 };
+                        let deflection = gamepads.iter()
+                            .filter_map(|gamepad| gamepad.get(*axis))
+                            .map(|axis_value| (axis_value * sign).max(0.0))
+                            .fold(0.0, f32::max);
+                        (deflection, false)
+                    }
+                };
+                value = value.max(binding_value);
+                just_pressed |= binding_just_pressed;
+            }
+            if value > 0.0 {
+                self.values.insert(action, value);
+            }
+            if just_pressed {
+                self.just_pressed.insert(action);
+            }
+        }
+    }
+}
+
+fn held(pressed: bool) -> f32 {
+    if pressed { 1.0 } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinding_replaces_only_the_same_device() {
+        let mut bindings = InputBindings::defaults();
+        bindings.rebind(Action::Serve, Binding::Key(KeyCode::KeyW));
+
+        assert_eq!(bindings.get(Action::Serve), [
+            Binding::Mouse(MouseButton::Left),
+            Binding::Gamepad(GamepadButton::South),
+            Binding::Key(KeyCode::KeyW),
+        ]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{prelude::*, math::bounding::{Aabb2d, BoundingVolume}};
+
+use crate::game::prelude::*;
+
+// Uniform grid over the colliders that stay put (walls and bricks), so a ball or laser only
+// tests the colliders in the cells it passes through. The paddle moves every tick and is
+// tested on its own instead.
+#[derive(Resource, Debug)]
+pub struct ColliderGrid {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<Entity>>,
+    entries: HashMap<Entity, GridEntry>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GridEntry {
+    // the cells the collider covers, so it can be removed after its transform is gone
+    covered: IRect,
+    center: Vec2,
+}
+
+impl Default for ColliderGrid {
+    fn default() -> Self {
+        Self::new(COLLIDER_GRID_CELL_SIZE)
+    }
+}
+
+impl ColliderGrid {
+    pub fn new(cell_size: f32) -> Self {
+        Self { cell_size, cells: HashMap::new(), entries: HashMap::new() }
+    }
+
+    // Inserting a collider again moves it to its new cells.
+    pub fn insert(&mut self, entity: Entity, bounding_box: Aabb2d) {
+        self.remove(entity);
+        let covered = self.cells_covering(bounding_box);
+        for cell in cell_iter(covered) {
+            self.cells.entry(cell).or_default().push(entity);
+        }
+        self.entries.insert(entity, GridEntry { covered, center: bounding_box.center() });
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        let Some(entry) = self.entries.remove(&entity) else { return; };
+        for cell in cell_iter(entry.covered) {
+            if let Some(entities) = self.cells.get_mut(&cell) {
+                entities.retain(|other| *other != entity);
+                if entities.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Every collider sharing a cell with `area`, each once, from the bottom up and then left to
+    // right. Entity ids depend on what was spawned and despawned before, so ordering by them
+    // would let a replay break bricks in a different order than the recorded run.
+    pub fn query(&self, area: Aabb2d) -> Vec<Entity> {
+        let mut found: Vec<(Vec2, Entity)> = cell_iter(self.cells_covering(area))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|entity| (self.entries[entity].center, *entity))
+            .collect();
+        // stable, so colliders at the same spot keep the cell order they were inserted in
+        found.sort_by(|(a, _), (b, _)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        let mut seen = HashSet::new();
+        found.into_iter().filter(|(_, entity)| seen.insert(*entity)).map(|(_, entity)| entity).collect()
+    }
+
+    fn cells_covering(&self, area: Aabb2d) -> IRect {
+        let min = (area.min / self.cell_size).floor().as_ivec2();
+        let max = (area.max / self.cell_size).floor().as_ivec2();
+        IRect::from_corners(min, max)
+    }
+}
+
+// cells from `min` to `max`, both included
+fn cell_iter(covered: IRect) -> impl Iterator<Item = IVec2> {
+    (covered.min.y..=covered.max.y)
+        .flat_map(move |y| (covered.min.x..=covered.max.x).map(move |x| IVec2::new(x, y)))
+}
+
+// The area a ball sweeps through while moving by `motion`.
+pub fn swept_area(center: Vec2, radius: f32, motion: Vec2) -> Aabb2d {
+    let start = Aabb2d::new(center, Vec2::splat(radius));
+    let end = Aabb2d::new(center + motion, Vec2::splat(radius));
+    start.merge(&end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(index: u32) -> Entity {
+        Entity::from_raw_u32(index).unwrap()
+    }
+
+    #[test]
+    fn query_finds_only_nearby_colliders() {
+        let mut grid = ColliderGrid::new(50.0);
+        grid.insert(entity(1), Aabb2d::new(Vec2::new(0.0, 0.0), Vec2::new(20.0, 10.0)));
+        grid.insert(entity(2), Aabb2d::new(Vec2::new(500.0, 0.0), Vec2::new(20.0, 10.0)));
+
+        assert_eq!(grid.query(Aabb2d::new(Vec2::new(10.0, 30.0), Vec2::splat(15.0))), vec![entity(1)]);
+        assert!(grid.query(Aabb2d::new(Vec2::new(250.0, 0.0), Vec2::splat(15.0))).is_empty());
+    }
+
+    #[test]
+    fn collider_spanning_many_cells_is_returned_once() {
+        // a wall much longer than a cell
+        let mut grid = ColliderGrid::new(50.0);
+        grid.insert(entity(1), Aabb2d::new(Vec2::ZERO, Vec2::new(5.0, 400.0)));
+
+        assert_eq!(grid.query(Aabb2d::new(Vec2::ZERO, Vec2::new(30.0, 300.0))), vec![entity(1)]);
+    }
+
+    #[test]
+    fn removed_and_moved_colliders_leave_their_old_cells() {
+        let mut grid = ColliderGrid::new(50.0);
+        grid.insert(entity(1), Aabb2d::new(Vec2::ZERO, Vec2::splat(10.0)));
+        grid.insert(entity(2), Aabb2d::new(Vec2::ZERO, Vec2::splat(10.0)));
+
+        grid.remove(entity(1));
+        grid.insert(entity(2), Aabb2d::new(Vec2::new(300.0, 0.0), Vec2::splat(10.0)));
+
+        assert_eq!(grid.len(), 1);
+        assert!(grid.query(Aabb2d::new(Vec2::ZERO, Vec2::splat(10.0))).is_empty());
+        assert_eq!(grid.query(Aabb2d::new(Vec2::new(300.0, 0.0), Vec2::splat(10.0))), vec![entity(2)]);
+    }
+
+    #[test]
+    fn query_orders_by_position_whatever_the_entity_ids() {
+        let mut grid = ColliderGrid::new(50.0);
+        grid.insert(entity(7), Aabb2d::new(Vec2::new(40.0, 0.0), Vec2::splat(10.0)));
+        grid.insert(entity(3), Aabb2d::new(Vec2::new(0.0, 30.0), Vec2::splat(10.0)));
+        grid.insert(entity(5), Aabb2d::new(Vec2::new(0.0, 0.0), Vec2::splat(10.0)));
+
+        assert_eq!(grid.query(Aabb2d::new(Vec2::new(20.0, 15.0), Vec2::splat(40.0))), vec![entity(5), entity(7), entity(3)]);
+    }
+
+    #[test]
+    fn swept_area_covers_both_ends_of_the_motion() {
+        let area = swept_area(Vec2::ZERO, 15.0, Vec2::new(100.0, -40.0));
+        assert_eq!(area.min, Vec2::new(-15.0, -55.0));
+        assert_eq!(area.max, Vec2::new(115.0, 15.0));
+    }
+}
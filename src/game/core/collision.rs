@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Corner {
+    UpperLeft,
+    UpperRight,
+    LowerLeft,
+    LowerRight,
+}
+
+// Which side of a collider the ball touched.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Collision {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Corner(Vec2, Corner),
+}
+
+impl Collision {
+    // surface normal pointing from the box towards the ball
+    pub fn normal(&self) -> Vec2 {
+        match self {
+            Collision::Left => Vec2::NEG_X,
+            Collision::Right => Vec2::X,
+            Collision::Top => Vec2::Y,
+            Collision::Bottom => Vec2::NEG_Y,
+            Collision::Corner(normal, _corner) => *normal,
+        }
+    }
+
+    pub(crate) fn from_normal(normal: Vec2, paddle_hit: bool) -> Self {
+        if normal.x != 0.0 && normal.y != 0.0 {
+            if paddle_hit && normal.y <= 0.0 {
+                return Collision::Bottom;
+            }
+            let corner = match (normal.x >= 0.0, normal.y >= 0.0) {
+                (true, true) => Corner::UpperRight,
+                (false, true) => Corner::UpperLeft,
+                (true, false) => Corner::LowerRight,
+                (false, false) => Corner::LowerLeft,
+            };
+            Collision::Corner(normal, corner)
+        } else if normal.x < 0.0 {
+            Collision::Left
+        } else if normal.x > 0.0 {
+            Collision::Right
+        } else if normal.y > 0.0 {
+            Collision::Top
+        } else {
+            Collision::Bottom
+        }
+    }
+}
@@ -7,12 +7,45 @@ pub struct StartUi;
 #[derive(Component)]
 pub struct Paddle;
 
+// physics ticks left before the ball can bounce off the paddle again
 #[derive(Component)]
-pub struct PaddleCooldown(pub f32);
+pub struct PaddleCooldown(pub u32);
+
+// horizontal speed of the paddle over the last frame, used to aim serves
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct PaddleMotion(pub f32);
 
 #[derive(Component)]
 pub struct Ball;
 
+// Bodies moved in `FixedUpdate` are drawn between their last two physics positions.
+// `Transform` holds the physics position while the fixed loop runs and the blended one otherwise.
+#[derive(Component)]
+pub struct Interpolated {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
+
+impl Interpolated {
+    pub fn new(translation: Vec3) -> Self {
+        Self { previous: translation, current: translation }
+    }
+}
+
+// the ball rests on the paddle until it is served
+#[derive(Component)]
+pub struct OnPaddle;
+
+// a lost ball stays hidden on the paddle until the timer runs out
+#[derive(Component, Deref, DerefMut)]
+pub struct RespawnCountdown(pub Timer);
+
+impl RespawnCountdown {
+    pub fn new(delay: f32) -> Self {
+        Self(Timer::from_seconds(delay, TimerMode::Once))
+    }
+}
+
 #[derive(Component, Deref, DerefMut)]
 pub struct Velocity(Vec2);
 
@@ -22,8 +55,123 @@ impl Velocity {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrickKind {
+    Normal,
+    // takes `hits` hits to break, fading towards the normal colour as it is damaged
+    Tough { hits: u32 },
+    // never breaks and does not count towards clearing the stage
+    Steel,
+    // breaks every destructible brick around it when destroyed
+    Explosive,
+}
+
+impl BrickKind {
+    pub fn max_health(&self) -> u32 {
+        match self {
+            BrickKind::Tough { hits } => *hits,
+            _ => 1,
+        }
+    }
+
+    pub fn is_destructible(&self) -> bool {
+        *self != BrickKind::Steel
+    }
+
+    pub fn score(&self) -> usize {
+        match self {
+            BrickKind::Normal => NORMAL_BRICK_SCORE,
+            BrickKind::Tough { hits } => TOUGH_BRICK_SCORE_PER_HIT * *hits as usize,
+            BrickKind::Steel => 0,
+            BrickKind::Explosive => EXPLOSIVE_BRICK_SCORE,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Brick {
+    pub kind: BrickKind,
+    pub health: u32,
+}
+
+impl Brick {
+    pub fn new(kind: BrickKind) -> Self {
+        Self { kind, health: kind.max_health() }
+    }
+
+    pub fn color(&self, config: &GameConfig) -> Color {
+        match self.kind {
+            BrickKind::Normal => config.brick_color,
+            BrickKind::Tough { hits } => {
+                let damage = 1.0 - self.health as f32 / hits as f32;
+                config.tough_brick_color.mix(&config.brick_color, damage)
+            }
+            BrickKind::Steel => config.steel_brick_color,
+            BrickKind::Explosive => config.explosive_brick_color,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerUpKind {
+    WidePaddle,
+    SlowBall,
+    // the paddle catches the ball, which then waits for a serve
+    StickyPaddle,
+    ExtraLife,
+    // the paddle fires upwards at the bricks
+    Laser,
+    // every ball in play splits into three
+    MultiBall,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 6] = [
+        PowerUpKind::WidePaddle,
+        PowerUpKind::SlowBall,
+        PowerUpKind::StickyPaddle,
+        PowerUpKind::ExtraLife,
+        PowerUpKind::Laser,
+        PowerUpKind::MultiBall,
+    ];
+
+    // `None` for effects that apply once instead of running for a while
+    pub fn duration(&self) -> Option<f32> {
+        match self {
+            PowerUpKind::ExtraLife | PowerUpKind::MultiBall => None,
+            _ => Some(POWER_UP_DURATION),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerUpKind::WidePaddle => "W",
+            PowerUpKind::SlowBall => "S",
+            PowerUpKind::StickyPaddle => "C",
+            PowerUpKind::ExtraLife => "+",
+            PowerUpKind::Laser => "L",
+            PowerUpKind::MultiBall => "M",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            PowerUpKind::WidePaddle => WIDE_PADDLE_POWER_UP_COLOR,
+            PowerUpKind::SlowBall => SLOW_BALL_POWER_UP_COLOR,
+            PowerUpKind::StickyPaddle => STICKY_PADDLE_POWER_UP_COLOR,
+            PowerUpKind::ExtraLife => EXTRA_LIFE_POWER_UP_COLOR,
+            PowerUpKind::Laser => LASER_POWER_UP_COLOR,
+            PowerUpKind::MultiBall => MULTI_BALL_POWER_UP_COLOR,
+        }
+    }
+}
+
+// falls from a destroyed brick until the paddle catches it or it leaves the arena
+#[derive(Component)]
+pub struct PowerUpCapsule(pub PowerUpKind);
+
 #[derive(Component)]
-pub struct Brick;
+pub struct LaserBolt;
 
 #[derive(Component)]
 pub struct BottomWall;
@@ -43,32 +191,35 @@ pub enum WallLocation {
 }
 
 impl WallLocation {
-    fn position(&self) -> Vec2 {
+    fn position(&self, config: &GameConfig) -> Vec2 {
+        let center_x = (config.left_wall + config.right_wall) / 2.0;
+        let center_y = (config.bottom_wall + config.top_wall) / 2.0;
+
         match self {
-            WallLocation::Left => Vec2::new(LEFT_WALL, 0.0),
-            WallLocation::Right => Vec2::new(RIGHT_WALL, 0.0),
-            WallLocation::Bottom => Vec2::new(0.0, BOTTOM_WALL),
-            WallLocation::Top => Vec2::new(0.0, TOP_WALL),
+            WallLocation::Left => Vec2::new(config.left_wall, center_y),
+            WallLocation::Right => Vec2::new(config.right_wall, center_y),
+            WallLocation::Bottom => Vec2::new(center_x, config.bottom_wall),
+            WallLocation::Top => Vec2::new(center_x, config.top_wall),
         }
     }
 
-    fn size(&self) -> Vec2 {
-        let arena_height = TOP_WALL - BOTTOM_WALL;
-        let arena_width = RIGHT_WALL - LEFT_WALL;
+    fn size(&self, config: &GameConfig) -> Vec2 {
+        let arena_height = config.arena_height();
+        let arena_width = config.arena_width();
         assert!(arena_height > 0.0);
         assert!(arena_width > 0.0);
 
         match self {
             WallLocation::Left | WallLocation::Right => {
                 Vec2::new(
-                    WALL_THICKNESS,
-                    arena_height + WALL_THICKNESS,
+                    config.wall_thickness,
+                    arena_height + config.wall_thickness,
                 )
             }
             WallLocation::Bottom | WallLocation::Top => {
                 Vec2::new(
-                    arena_width + WALL_THICKNESS,
-                    WALL_THICKNESS,
+                    arena_width + config.wall_thickness,
+                    config.wall_thickness,
                 )
             }
         }
@@ -76,13 +227,13 @@ impl WallLocation {
 }
 
 impl Wall {
-    pub fn new(location: WallLocation) -> (Wall, Sprite, Transform) {
+    pub fn new(location: WallLocation, config: &GameConfig) -> (Wall, Sprite, Transform) {
         (
             Wall,
-            Sprite::from_color(WALL_COLOR, Vec2::ONE),
+            Sprite::from_color(config.wall_color, Vec2::ONE),
             Transform {
-                translation: location.position().extend(0.0),
-                scale: location.size().extend(1.0),
+                translation: location.position(config).extend(0.0),
+                scale: location.size(config).extend(1.0),
                 ..default()
             },
         )
@@ -90,4 +241,60 @@ impl Wall {
 }
 
 #[derive(Component)]
-pub struct ScoreboardUi;
\ No newline at end of file
+pub struct ScoreboardUi;
+
+#[derive(Component)]
+pub struct CountdownUi;
+
+#[derive(Component)]
+pub struct NameEntryUi;
+
+// why the campaign could not be loaded, shown on the menu in place of a game
+#[derive(Component)]
+pub struct LoadErrorUi;
+
+#[derive(Component)]
+pub struct DebugReadout;
+
+// "+N xM" text rising from a destroyed brick until the timer runs out
+#[derive(Component, Deref, DerefMut)]
+pub struct ScorePopup(pub Timer);
+
+// one line of the controls screen: the inputs bound to an action, or how the paddle is steered
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettingsRow {
+    Binding(Action),
+    PaddleControl,
+}
+
+impl SettingsRow {
+    // every action, then the paddle control
+    pub const COUNT: usize = Action::ALL.len() + 1;
+
+    pub fn at(row: usize) -> Self {
+        Action::ALL.get(row).map_or(SettingsRow::PaddleControl, |action| SettingsRow::Binding(*action))
+    }
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PauseMenuOption {
+    Resume,
+    Restart,
+    QuitToMenu,
+}
+
+impl PauseMenuOption {
+    pub const ALL: [PauseMenuOption; 3] = [
+        PauseMenuOption::Resume,
+        PauseMenuOption::Restart,
+        PauseMenuOption::QuitToMenu,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PauseMenuOption::Resume => "Resume",
+            PauseMenuOption::Restart => "Restart Stage",
+            PauseMenuOption::QuitToMenu => "Quit to Menu",
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::{fmt, path::{Path, PathBuf}};
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::game::prelude::*;
+
+/// Gameplay tunables that can be changed without recompiling.
+///
+/// Read from `settings.ron` in the user's config directory unless one is handed to `GamePlugin`.
+/// The game binary builds it with [`GameConfig::from_args`]: the file given with `--config <path>`,
+/// then `--<field-name> <value>` overrides, e.g. `--ball-speed 600` or `--paddle-size "(160, 20)"`.
+/// Values are written as RON and any field left out keeps its default from `core/constants.rs`.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct GameConfig {
+    pub paddle_size: Vec2,
+    pub paddle_speed: f32,
+    pub paddle_padding: f32,
+    // physics ticks between two paddle bounces of the same ball
+    pub paddle_hit_cooldown_ticks: u32,
+    // degrees from horizontal for hits at the paddle's edge and at its centre
+    pub paddle_bounce_min_angle: f32,
+    pub paddle_bounce_max_angle: f32,
+    pub paddle_control: PaddleControl,
+    pub pointer_max_speed: f32,
+    pub ball_diameter: f32,
+    pub ball_speed: f32,
+    pub ball_respawn_delay: f32,
+    pub starting_lives: usize,
+    pub left_wall: f32,
+    pub right_wall: f32,
+    pub bottom_wall: f32,
+    pub top_wall: f32,
+    pub wall_thickness: f32,
+    pub gap_between_paddle_and_floor: f32,
+    pub gap_between_bricks_and_ceiling: f32,
+    // physics ticks per second, read once when the game starts
+    pub physics_tick_rate: f64,
+    pub paddle_color: Color,
+    pub ball_color: Color,
+    pub wall_color: Color,
+    pub brick_color: Color,
+    // undamaged tough bricks, fading to `brick_color` as they are hit
+    pub tough_brick_color: Color,
+    pub steel_brick_color: Color,
+    pub explosive_brick_color: Color,
+    pub text_color: Color,
+    // titles and the selected entry of a menu
+    pub title_text_color: Color,
+    pub score_color: Color,
+}
+
+// How the player steers the paddle, `paddle_control: Pointer` in the settings file.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaddleControl {
+    // arrow keys and gamepads
+    #[default]
+    Buttons,
+    // the paddle follows the mouse cursor or a finger, and a click or tap serves
+    Pointer,
+}
+
+impl PaddleControl {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaddleControl::Buttons => "Buttons",
+            PaddleControl::Pointer => "Mouse or Touch",
+        }
+    }
+
+    pub fn toggled(&self) -> Self {
+        match self {
+            PaddleControl::Buttons => PaddleControl::Pointer,
+            PaddleControl::Pointer => PaddleControl::Buttons,
+        }
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            paddle_size: PADDLE_SIZE,
+            paddle_speed: PADDLE_SPEED,
+            paddle_padding: PADDLE_PADDING,
+            paddle_hit_cooldown_ticks: PADDLE_HIT_COOLDOWN_TICKS,
+            paddle_bounce_min_angle: PADDLE_BOUNCE_MIN_ANGLE,
+            paddle_bounce_max_angle: PADDLE_BOUNCE_MAX_ANGLE,
+            paddle_control: PaddleControl::default(),
+            pointer_max_speed: POINTER_MAX_SPEED,
+            ball_diameter: BALL_DIAMETER,
+            ball_speed: BALL_SPEED,
+            ball_respawn_delay: BALL_RESPAWN_DELAY,
+            starting_lives: STARTING_LIVES,
+            left_wall: LEFT_WALL,
+            right_wall: RIGHT_WALL,
+            bottom_wall: BOTTOM_WALL,
+            top_wall: TOP_WALL,
+            wall_thickness: WALL_THICKNESS,
+            gap_between_paddle_and_floor: GAP_BETWEEN_PADDLE_AND_FLOOR,
+            gap_between_bricks_and_ceiling: GAP_BETWEEN_BRICKS_AND_CEILING,
+            physics_tick_rate: PHYSICS_TICK_RATE_HZ,
+            paddle_color: PADDLE_COLOR,
+            ball_color: BALL_COLOR,
+            wall_color: WALL_COLOR,
+            brick_color: BRICK_COLOR,
+            tough_brick_color: TOUGH_BRICK_COLOR,
+            steel_brick_color: STEEL_BRICK_COLOR,
+            explosive_brick_color: EXPLOSIVE_BRICK_COLOR,
+            text_color: TEXT_COLOR,
+            title_text_color: TITLE_TEXT_COLOR,
+            score_color: SCORE_COLOR,
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn default_path() -> PathBuf {
+        config_path(SETTINGS_FILE)
+    }
+
+    // Problems are logged and skipped so a bad setting never stops the game from starting.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        let mut path = Self::default_path();
+        let mut overrides = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                warn!("Ignoring argument {arg:?}");
+                continue;
+            };
+            let (flag, value) = match flag.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (flag.to_string(), args.next()),
+            };
+            let Some(value) = value else {
+                warn!("Ignoring --{flag}: {}", ConfigError::MissingValue);
+                continue;
+            };
+            match flag.as_str() {
+                "config" => path = PathBuf::from(value),
+                _ => overrides.push((flag, value)),
+            }
+        }
+
+        let mut config = Self::from_file(&path);
+        for (flag, value) in overrides {
+            let mut overridden = config.clone();
+            match overridden.apply_override(&flag, &value).and_then(|()| overridden.validate()) {
+                Ok(()) => config = overridden,
+                Err(err) => warn!("Ignoring --{flag}: {err}"),
+            }
+        }
+        config
+    }
+
+    // The settings in `path`, or the defaults when they cannot be used.
+    pub fn from_file(path: &Path) -> Self {
+        Self::load(path).unwrap_or_else(|err| {
+            warn!("Using default settings instead of {}: {err}", path.display());
+            Self::default()
+        })
+    }
+
+    // A missing file means the defaults, a file with a bad setting is an error.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let Some(config) = load_ron::<Self>(path)? else { return Ok(Self::default()); };
+        config.validate()?;
+        Ok(config)
+    }
+
+    // Rejects values the simulation cannot run with, such as walls that cross or a paddle that cannot move.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let positive = [
+            ("paddle-size", self.paddle_size.min_element()),
+            ("paddle-speed", self.paddle_speed),
+            ("pointer-max-speed", self.pointer_max_speed),
+            ("ball-diameter", self.ball_diameter),
+            ("ball-speed", self.ball_speed),
+            ("wall-thickness", self.wall_thickness),
+            ("physics-tick-rate", self.physics_tick_rate as f32),
+            // the distance between opposite walls
+            ("right-wall", self.arena_width()),
+            ("top-wall", self.arena_height()),
+        ];
+        let non_negative = [
+            ("paddle-padding", self.paddle_padding),
+            ("ball-respawn-delay", self.ball_respawn_delay),
+            ("gap-between-paddle-and-floor", self.gap_between_paddle_and_floor),
+            ("gap-between-bricks-and-ceiling", self.gap_between_bricks_and_ceiling),
+        ];
+
+        if let Some((setting, _)) = positive.iter().find(|(_, value)| !(value.is_finite() && *value > 0.0)) {
+            return Err(ConfigError::Invalid { setting, requirement: "above zero" });
+        }
+        if let Some((setting, _)) = non_negative.iter().find(|(_, value)| !(value.is_finite() && *value >= 0.0)) {
+            return Err(ConfigError::Invalid { setting, requirement: "zero or more" });
+        }
+        if !(0.0 < self.paddle_bounce_min_angle && self.paddle_bounce_min_angle <= self.paddle_bounce_max_angle && self.paddle_bounce_max_angle <= 90.0) {
+            return Err(ConfigError::Invalid { setting: "paddle-bounce-min-angle", requirement: "above 0 and at most paddle-bounce-max-angle, itself at most 90" });
+        }
+        if self.starting_lives == 0 {
+            return Err(ConfigError::Invalid { setting: "starting-lives", requirement: "at least 1" });
+        }
+        Ok(())
+    }
+
+    // `flag` is a field name with dashes, `value` a RON value
+    pub fn apply_override(&mut self, flag: &str, value: &str) -> Result<(), ConfigError> {
+        match flag {
+            "paddle-size" => self.paddle_size = parse(value)?,
+            "paddle-speed" => self.paddle_speed = parse(value)?,
+            "paddle-padding" => self.paddle_padding = parse(value)?,
+            "paddle-hit-cooldown-ticks" => self.paddle_hit_cooldown_ticks = parse(value)?,
+            "paddle-bounce-min-angle" => self.paddle_bounce_min_angle = parse(value)?,
+            "paddle-bounce-max-angle" => self.paddle_bounce_max_angle = parse(value)?,
+            "paddle-control" => self.paddle_control = parse(value)?,
+            "pointer-max-speed" => self.pointer_max_speed = parse(value)?,
+            "ball-diameter" => self.ball_diameter = parse(value)?,
+            "ball-speed" => self.ball_speed = parse(value)?,
+            "ball-respawn-delay" => self.ball_respawn_delay = parse(value)?,
+            "starting-lives" => self.starting_lives = parse(value)?,
+            "left-wall" => self.left_wall = parse(value)?,
+            "right-wall" => self.right_wall = parse(value)?,
+            "bottom-wall" => self.bottom_wall = parse(value)?,
+            "top-wall" => self.top_wall = parse(value)?,
+            "wall-thickness" => self.wall_thickness = parse(value)?,
+            "gap-between-paddle-and-floor" => self.gap_between_paddle_and_floor = parse(value)?,
+            "gap-between-bricks-and-ceiling" => self.gap_between_bricks_and_ceiling = parse(value)?,
+            "physics-tick-rate" => self.physics_tick_rate = parse(value)?,
+            "paddle-color" => self.paddle_color = parse(value)?,
+            "ball-color" => self.ball_color = parse(value)?,
+            "wall-color" => self.wall_color = parse(value)?,
+            "brick-color" => self.brick_color = parse(value)?,
+            "tough-brick-color" => self.tough_brick_color = parse(value)?,
+            "steel-brick-color" => self.steel_brick_color = parse(value)?,
+            "explosive-brick-color" => self.explosive_brick_color = parse(value)?,
+            "text-color" => self.text_color = parse(value)?,
+            "title-text-color" => self.title_text_color = parse(value)?,
+            "score-color" => self.score_color = parse(value)?,
+            _ => return Err(ConfigError::UnknownSetting),
+        }
+        Ok(())
+    }
+
+    pub fn arena_width(&self) -> f32 {
+        self.right_wall - self.left_wall
+    }
+
+    pub fn arena_height(&self) -> f32 {
+        self.top_wall - self.bottom_wall
+    }
+}
+
+fn parse<T: DeserializeOwned>(value: &str) -> Result<T, ConfigError> {
+    Ok(ron::from_str(value)?)
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    File(RonFileError),
+    Ron(ron::error::SpannedError),
+    UnknownSetting,
+    MissingValue,
+    Invalid { setting: &'static str, requirement: &'static str },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::File(err) => write!(f, "settings file: {err}"),
+            ConfigError::Ron(err) => write!(f, "could not parse setting: {err}"),
+            ConfigError::UnknownSetting => write!(f, "no such setting"),
+            ConfigError::MissingValue => write!(f, "missing value"),
+            ConfigError::Invalid { setting, requirement } => write!(f, "{setting} must be {requirement}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<RonFileError> for ConfigError {
+    fn from(err: RonFileError) -> Self {
+        ConfigError::File(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for ConfigError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        ConfigError::Ron(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // never reads the player's own settings file
+    fn from_args(args: &[&str]) -> GameConfig {
+        let missing = std::env::temp_dir().join(format!("kuzusi-{}-no-settings.ron", std::process::id()));
+        let mut all_args = vec!["--config".to_string(), missing.display().to_string()];
+        all_args.extend(args.iter().map(|arg| arg.to_string()));
+        GameConfig::from_args(all_args)
+    }
+
+    #[test]
+    fn overrides_accept_separate_and_joined_values() {
+        let config = from_args(&["--ball-speed", "600", "--paddle-size=(160, 20)", "--paddle-control", "Pointer"]);
+        assert_eq!(config.ball_speed, 600.0);
+        assert_eq!(config.paddle_size, Vec2::new(160.0, 20.0));
+        assert_eq!(config.paddle_control, PaddleControl::Pointer);
+    }
+
+    #[test]
+    fn unknown_malformed_and_valueless_overrides_are_skipped() {
+        let config = from_args(&["stray", "--no-such-setting", "1", "--ball-speed", "fast", "--starting-lives"]);
+        assert_eq!(config, GameConfig::default());
+    }
+
+    #[test]
+    fn overrides_that_break_the_game_are_skipped() {
+        let config = from_args(&["--paddle-speed", "0", "--ball-diameter", "-5", "--left-wall", "0", "--right-wall", "-10"]);
+        let defaults = GameConfig::default();
+        assert_eq!(config.paddle_speed, defaults.paddle_speed);
+        assert_eq!(config.ball_diameter, defaults.ball_diameter);
+        // the left wall alone still leaves an arena, the right wall would cross it
+        assert_eq!(config.left_wall, 0.0);
+        assert_eq!(config.right_wall, defaults.right_wall);
+    }
+
+    #[test]
+    fn apply_override_reports_what_went_wrong() {
+        let mut config = GameConfig::default();
+        assert!(matches!(config.apply_override("ball-speed", "600.0"), Ok(())));
+        assert!(matches!(config.apply_override("ball-speeed", "600.0"), Err(ConfigError::UnknownSetting)));
+        assert!(matches!(config.apply_override("ball-speed", "\"fast\""), Err(ConfigError::Ron(_))));
+
+        config.apply_override("paddle-bounce-min-angle", "95.0").unwrap();
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid { setting: "paddle-bounce-min-angle", .. })));
+    }
+
+    #[test]
+    fn settings_file_with_a_bad_value_is_rejected() {
+        let path = std::env::temp_dir().join(format!("kuzusi-{}-bad-settings.ron", std::process::id()));
+        std::fs::write(&path, "(ball_speed: 0.0)").unwrap();
+        let loaded = GameConfig::load(&path);
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(loaded, Err(ConfigError::Invalid { setting: "ball-speed", .. })));
+    }
+}
@@ -1,13 +1,23 @@
 use bevy::prelude::*;
 
+// Paddle, ball, arena, brick and text colour values below are the defaults of `GameConfig`;
+// systems read the resource rather than these constants.
+
 pub(crate) const PADDLE_SIZE: Vec2 = Vec2::new(120.0, 20.0);
 pub(crate) const PADDLE_SPEED: f32 = 500.0;
 pub(crate) const PADDLE_PADDING: f32 = 10.0;
+// how fast the paddle may chase the cursor or a finger in pointer mode
+pub(crate) const POINTER_MAX_SPEED: f32 = 1500.0;
 
-pub(crate) const BALL_STARTING_POSITION: Vec3 = Vec3::new(0.0, -50.0, 1.0);
+pub(crate) const BALL_Z: f32 = 1.0;
 pub(crate) const BALL_DIAMETER: f32 = 30.0;
 pub(crate) const BALL_SPEED: f32 = 400.0;
-pub(crate) const INITIAL_BALL_DIRECTION: Vec2 = Vec2::new(0.5, -0.5);
+// served balls leave the paddle at most this far from vertical, depending on paddle motion
+pub(crate) const SERVE_MAX_ANGLE: f32 = std::f32::consts::FRAC_PI_4;
+pub(crate) const GAP_BETWEEN_BALL_AND_PADDLE: f32 = 1.0;
+pub(crate) const BALL_RESPAWN_DELAY: f32 = 3.0;
+
+pub(crate) const STARTING_LIVES: usize = 3;
 
 pub(crate) const WALL_THICKNESS: f32 = 10.0;
 pub(crate) const LEFT_WALL: f32 = -450.0;
@@ -18,23 +28,95 @@ pub(crate) const TOP_WALL: f32 = 300.0;
 pub(crate) const BRICK_SIZE: Vec2 = Vec2::new(100.0, 30.0);
 pub(crate) const GAP_BETWEEN_BRICKS: f32 = 5.0;
 pub(crate) const GAP_BETWEEN_PADDLE_AND_FLOOR: f32 = 60.0;
-pub(crate) const GAP_BETWEEN_PADDLE_AND_BRICKS: f32 = 270.0;
-pub(crate) const GAP_BETWEEN_BRICKS_AND_SIDES: f32 = 20.0;
 pub(crate) const GAP_BETWEEN_BRICKS_AND_CEILING: f32 = 20.0;
 
-pub(crate) const CORNER_THRESHOLD: f32 = 5.0;
+pub(crate) const NORMAL_BRICK_SCORE: usize = 1;
+pub(crate) const TOUGH_BRICK_SCORE_PER_HIT: usize = 2;
+pub(crate) const EXPLOSIVE_BRICK_SCORE: usize = 3;
+// the multiplier goes up by one every this many brick hits without touching the paddle
+pub(crate) const COMBO_HITS_PER_MULTIPLIER: usize = 4;
+pub(crate) const MAX_COMBO_MULTIPLIER: usize = 5;
+// explosions reach bricks whose centres are within this many brick sizes, i.e. the 8 neighbours
+pub(crate) const EXPLOSION_REACH: f32 = 1.5;
+
+pub(crate) const POWER_UP_DROP_CHANCE: f32 = 0.15;
+pub(crate) const POWER_UP_SEED: u64 = 0x6b75_7a75_7369;
+pub(crate) const POWER_UP_SIZE: Vec2 = Vec2::new(40.0, 16.0);
+pub(crate) const POWER_UP_FALL_SPEED: f32 = 150.0;
+pub(crate) const POWER_UP_DURATION: f32 = 10.0;
+pub(crate) const WIDE_PADDLE_FACTOR: f32 = 1.5;
+pub(crate) const SLOW_BALL_FACTOR: f32 = 0.6;
+pub(crate) const LASER_FIRE_INTERVAL: f32 = 0.4;
+pub(crate) const LASER_SPEED: f32 = 800.0;
+pub(crate) const LASER_SIZE: Vec2 = Vec2::new(4.0, 16.0);
+// the two extra balls of a split leave at this angle either side of the original
+pub(crate) const MULTI_BALL_SPREAD_ANGLE: f32 = 20.0 * std::f32::consts::PI / 180.0;
+
+pub(crate) const CAMPAIGN_PATH: &str = "levels/campaign.ron";
+
+// per-user files go in this folder of the user's data and config directories,
+// e.g. ~/.local/share/kuzusi and ~/.config/kuzusi on Linux
+pub(crate) const APP_DATA_DIR: &str = "kuzusi";
+pub(crate) const HIGH_SCORES_FILE: &str = "highscores.ron";
+pub(crate) const SETTINGS_FILE: &str = "settings.ron";
+pub(crate) const BINDINGS_FILE: &str = "bindings.ron";
+pub(crate) const REPLAY_FILE: &str = "replay.ron";
+// seconds between saves of the stage being recorded
+pub(crate) const REPLAY_FLUSH_INTERVAL: f32 = 5.0;
+pub(crate) const MAX_HIGH_SCORES: usize = 10;
+pub(crate) const HIGH_SCORE_NAME_MAX_LEN: usize = 10;
+pub(crate) const HIGH_SCORE_DEFAULT_NAME: &str = "PLAYER";
 
-pub(crate) const PADDLE_HIT_COOLDOWN: f32 = 0.5;
+pub(crate) const PHYSICS_TICK_RATE_HZ: f64 = 120.0;
+
+// bounces resolved for one ball in a single frame before the rest of its movement is dropped
+pub(crate) const MAX_COLLISIONS_PER_FRAME: usize = 4;
+// contacts this close along the ball's path (in world units) are resolved as one simultaneous hit
+pub(crate) const CONTACT_SLOP: f32 = 0.01;
+// side of a `ColliderGrid` cell, about the size of a small brick
+pub(crate) const COLLIDER_GRID_CELL_SIZE: f32 = 64.0;
+
+// outgoing angles off the paddle in degrees, measured from horizontal: edge hits and centre hits
+pub(crate) const PADDLE_BOUNCE_MIN_ANGLE: f32 = 30.0;
+pub(crate) const PADDLE_BOUNCE_MAX_ANGLE: f32 = 90.0;
+
+// in physics ticks, so it does not change with the tick rate; balls are pushed out of the paddle, so
+// this only guards against a ball caught between the paddle and a wall being bounced on consecutive ticks
+pub(crate) const PADDLE_HIT_COOLDOWN_TICKS: u32 = 2;
 
 pub(crate) const SCOREBOARD_FONT_SIZE: f32 = 33.0;
 pub(crate) const SCOREBOARD_TEXT_PADDING: Val = Val::Px(5.0);
 
+pub(crate) const POWER_UP_FONT_SIZE: f32 = 14.0;
 pub(crate) const START_UI_TITLE_FONT_SIZE: f32 = 100.0;
+pub(crate) const COUNTDOWN_FONT_SIZE: f32 = 80.0;
+pub(crate) const HIGH_SCORE_FONT_SIZE: f32 = 20.0;
+pub(crate) const SCORE_POPUP_FONT_SIZE: f32 = 18.0;
+pub(crate) const SCORE_POPUP_DURATION: f32 = 0.8;
+pub(crate) const SCORE_POPUP_RISE_SPEED: f32 = 60.0;
+pub(crate) const DEBUG_FONT_SIZE: f32 = 16.0;
+// the velocity arrow shows where the ball will be this many seconds from now
+pub(crate) const DEBUG_VELOCITY_SCALE: f32 = 0.25;
+pub(crate) const DEBUG_NORMAL_LENGTH: f32 = 30.0;
 
 pub(crate) const PADDLE_COLOR: Color = Color::srgb(0.3, 0.3, 0.7);
 pub(crate) const BALL_COLOR: Color = Color::srgb(0.1, 0.5, 0.5);
-pub(crate) const BRICK_COLOR: Color = Color::srgb(0.5, 0.5, 1.0);
 pub(crate) const WALL_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+pub(crate) const BRICK_COLOR: Color = Color::srgb(0.5, 0.5, 1.0);
+pub(crate) const TOUGH_BRICK_COLOR: Color = Color::srgb(0.15, 0.15, 0.6);
+pub(crate) const STEEL_BRICK_COLOR: Color = Color::srgb(0.45, 0.45, 0.5);
+pub(crate) const EXPLOSIVE_BRICK_COLOR: Color = Color::srgb(1.0, 0.45, 0.1);
 pub(crate) const TEXT_COLOR: Color = Color::srgb(0.5, 0.5, 1.0);
 pub(crate) const TITLE_TEXT_COLOR: Color = Color::srgb(1.0, 1.0, 0.5);
-pub(crate) const SCORE_COLOR: Color = Color::srgb(1.0, 0.5, 0.5);
\ No newline at end of file
+pub(crate) const SCORE_COLOR: Color = Color::srgb(1.0, 0.5, 0.5);
+// power-up, laser and debug colours are fixed
+pub(crate) const WIDE_PADDLE_POWER_UP_COLOR: Color = Color::srgb(0.3, 0.8, 0.3);
+pub(crate) const SLOW_BALL_POWER_UP_COLOR: Color = Color::srgb(0.2, 0.7, 0.9);
+pub(crate) const STICKY_PADDLE_POWER_UP_COLOR: Color = Color::srgb(0.8, 0.3, 0.8);
+pub(crate) const EXTRA_LIFE_POWER_UP_COLOR: Color = Color::srgb(0.9, 0.2, 0.3);
+pub(crate) const LASER_POWER_UP_COLOR: Color = Color::srgb(0.95, 0.85, 0.2);
+pub(crate) const MULTI_BALL_POWER_UP_COLOR: Color = Color::srgb(0.95, 0.95, 0.95);
+pub(crate) const LASER_COLOR: Color = Color::srgb(1.0, 0.3, 0.2);
+pub(crate) const DEBUG_COLLIDER_COLOR: Color = Color::srgb(0.2, 1.0, 0.2);
+pub(crate) const DEBUG_BALL_COLOR: Color = Color::srgb(1.0, 1.0, 0.2);
+pub(crate) const DEBUG_CONTACT_COLOR: Color = Color::srgb(1.0, 0.2, 0.2);
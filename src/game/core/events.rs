@@ -1,4 +1,19 @@
 use bevy::prelude::*;
 
+use crate::game::core::collision::Collision;
+
+// `point` is where the ball's edge touched, `normal` points from the collider towards the ball
+#[derive(Event, Clone, Copy, Debug)]
+pub struct BallCollided {
+    pub point: Vec2,
+    pub normal: Vec2,
+    pub collision: Collision,
+}
+
+// `points` is the brick's own value, the score went up by `points * multiplier`
 #[derive(Event)]
-pub struct BallCollided;
\ No newline at end of file
+pub struct BrickDestroyed {
+    pub position: Vec2,
+    pub points: usize,
+    pub multiplier: usize,
+}
@@ -0,0 +1,189 @@
+use std::{fs, path::{Path, PathBuf}, time::{SystemTime, UNIX_EPOCH}};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: usize,
+    // 1-based stage the run ended on
+    pub level: usize,
+    // UTC, formatted as YYYY-MM-DD
+    pub date: String,
+}
+
+// Best runs first, at most `MAX_HIGH_SCORES` of them.
+#[derive(Resource, Serialize, Deserialize, Debug)]
+pub struct HighScores {
+    entries: Vec<HighScoreEntry>,
+}
+
+// Where the table is read from and written to.
+#[derive(Resource, Deref)]
+pub struct HighScoreFile(pub PathBuf);
+
+impl Default for HighScoreFile {
+    fn default() -> Self {
+        Self(data_path(HIGH_SCORES_FILE))
+    }
+}
+
+// Loaded while the plugins are built so the start screen can show it on the first `OnEnter(GameState::Menu)`.
+// A file that cannot be read is moved aside and the game starts with an empty table.
+impl FromWorld for HighScores {
+    fn from_world(world: &mut World) -> Self {
+        let path = &world.resource::<HighScoreFile>().0;
+        match HighScores::load(path) {
+            Ok(high_scores) => high_scores,
+            Err(err) => {
+                warn!("Ignoring high scores at {}: {err}", path.display());
+                let backup = path.with_extension("ron.bak");
+                if let Err(err) = fs::rename(path, &backup) {
+                    warn!("Could not move {} aside: {err}", path.display());
+                }
+                HighScores { entries: Vec::new() }
+            }
+        }
+    }
+}
+
+impl HighScores {
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    pub fn qualifies(&self, score: usize) -> bool {
+        score > 0
+            && (self.entries.len() < MAX_HIGH_SCORES
+                || self.entries.last().is_some_and(|lowest| score > lowest.score))
+    }
+
+    // Returns the 0-based rank of the new entry, or `None` when it did not make the table.
+    // Ties keep the older entry ahead.
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self.entries.iter().position(|existing| entry.score > existing.score).unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+
+    // A missing file is an empty table; anything else that goes wrong is an error.
+    pub fn load(path: &Path) -> Result<Self, RonFileError> {
+        let Some(mut high_scores) = load_ron::<HighScores>(path)? else { return Ok(Self { entries: Vec::new() }); };
+        // the file may have been edited by hand
+        high_scores.entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        high_scores.entries.truncate(MAX_HIGH_SCORES);
+        Ok(high_scores)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), RonFileError> {
+        save_ron(path, self)
+    }
+}
+
+pub fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or(0) as i64;
+    civil_date(days)
+}
+
+// days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: usize) -> HighScoreEntry {
+        HighScoreEntry { name: name.to_string(), score, level: 1, date: "2026-01-01".to_string() }
+    }
+
+    // a file of its own for each test, since tests run in parallel
+    fn temp_path(test: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("kuzusi-{}-{test}.ron", std::process::id()))
+    }
+
+    #[test]
+    fn civil_date_starts_at_the_epoch() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(-1), "1969-12-31");
+    }
+
+    #[test]
+    fn civil_date_handles_leap_days() {
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(19_782), "2024-02-29");
+        assert_eq!(civil_date(19_783), "2024-03-01");
+    }
+
+    #[test]
+    fn ties_keep_the_older_entry_ahead() {
+        let mut high_scores = HighScores { entries: Vec::new() };
+        assert_eq!(high_scores.insert(entry("first", 50)), Some(0));
+        assert_eq!(high_scores.insert(entry("higher", 80)), Some(0));
+        assert_eq!(high_scores.insert(entry("tied", 50)), Some(2));
+
+        let names: Vec<&str> = high_scores.entries().iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, ["higher", "first", "tied"]);
+    }
+
+    #[test]
+    fn full_table_only_takes_scores_above_the_lowest() {
+        let mut high_scores = HighScores { entries: Vec::new() };
+        for score in 1..=MAX_HIGH_SCORES {
+            high_scores.insert(entry("filler", score * 10));
+        }
+        assert!(!high_scores.qualifies(0));
+        assert!(!high_scores.qualifies(10));
+        assert_eq!(high_scores.insert(entry("tied lowest", 10)), None);
+        assert_eq!(high_scores.insert(entry("new", 15)), Some(MAX_HIGH_SCORES - 1));
+        assert_eq!(high_scores.entries().len(), MAX_HIGH_SCORES);
+        assert_eq!(high_scores.entries().last().unwrap().score, 15);
+    }
+
+    #[test]
+    fn missing_file_is_an_empty_table() {
+        let high_scores = HighScores::load(&temp_path("missing-highscores")).unwrap();
+        assert!(high_scores.entries().is_empty());
+    }
+
+    #[test]
+    fn truncated_file_is_moved_aside_for_an_empty_table() {
+        let path = temp_path("truncated-highscores");
+        let mut high_scores = HighScores { entries: Vec::new() };
+        high_scores.insert(entry("saved", 40));
+        high_scores.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        fs::write(&path, &text[..text.len() / 2]).unwrap();
+        assert!(HighScores::load(&path).is_err());
+
+        let mut world = World::new();
+        world.insert_resource(HighScoreFile(path.clone()));
+        let loaded = HighScores::from_world(&mut world);
+
+        assert!(loaded.entries().is_empty());
+        let backup = path.with_extension("ron.bak");
+        assert_eq!(fs::read_to_string(&backup).unwrap(), text[..text.len() / 2]);
+        let _ = fs::remove_file(backup);
+    }
+}
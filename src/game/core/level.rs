@@ -0,0 +1,269 @@
+use std::fmt;
+
+use bevy::{asset::{io::Reader, AssetLoader, LoadContext}, prelude::*};
+use serde::Deserialize;
+
+use crate::game::prelude::*;
+
+#[derive(Asset, TypePath, Debug)]
+pub struct Level {
+    pub name: String,
+    pub brick_size: Vec2,
+    pub gap: f32,
+    pub columns: usize,
+    pub bricks: Vec<LevelBrick>,
+}
+
+// Ordered list of stages played one after another.
+#[derive(Asset, TypePath, Debug)]
+pub struct Campaign {
+    pub levels: Vec<Handle<Level>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LevelBrick {
+    pub row: usize,
+    pub column: usize,
+    pub kind: BrickKind,
+}
+
+impl Level {
+    // Rows are laid out downwards from the ceiling, the grid is centred horizontally.
+    pub fn brick_positions(&self, config: &GameConfig) -> impl Iterator<Item = (Vec2, &LevelBrick)> {
+        let center_of_bricks = (config.left_wall + config.right_wall) / 2.0;
+        let n_vertical_gaps = self.columns.saturating_sub(1);
+        let left_edge_of_bricks = center_of_bricks
+            - (self.columns as f32 / 2.0 * self.brick_size.x)
+            - n_vertical_gaps as f32 / 2.0 * self.gap;
+        let top_edge_of_bricks = config.top_wall - config.gap_between_bricks_and_ceiling;
+
+        let offset_x = left_edge_of_bricks + self.brick_size.x / 2.0;
+        let offset_y = top_edge_of_bricks - self.brick_size.y / 2.0;
+
+        self.bricks.iter().map(move |brick| {
+            let position = Vec2::new(
+                offset_x + brick.column as f32 * (self.brick_size.x + self.gap),
+                offset_y - brick.row as f32 * (self.brick_size.y + self.gap),
+            );
+            (position, brick)
+        })
+    }
+
+    // The area the bricks cover once placed in the arena, `None` for a level without bricks.
+    pub fn bounds(&self, config: &GameConfig) -> Option<Rect> {
+        self.brick_positions(config)
+            .map(|(position, _)| Rect::from_center_size(position, self.brick_size))
+            .reduce(|bounds, brick| bounds.union(brick))
+    }
+
+    // Bricks must stay clear of the side walls and above the paddle.
+    pub fn fits(&self, config: &GameConfig) -> bool {
+        let Some(bounds) = self.bounds(config) else { return true; };
+        let paddle_top = config.bottom_wall + config.gap_between_paddle_and_floor + config.paddle_size.y / 2.0;
+        bounds.min.x >= config.left_wall + config.wall_thickness / 2.0
+            && bounds.max.x <= config.right_wall - config.wall_thickness / 2.0
+            && bounds.min.y > paddle_top
+    }
+}
+
+#[derive(Deserialize)]
+struct LevelFile {
+    name: String,
+    #[serde(default = "default_brick_size")]
+    brick_size: (f32, f32),
+    #[serde(default = "default_gap")]
+    gap: f32,
+    layout: Vec<String>,
+}
+
+fn default_brick_size() -> (f32, f32) {
+    (BRICK_SIZE.x, BRICK_SIZE.y)
+}
+
+fn default_gap() -> f32 {
+    GAP_BETWEEN_BRICKS
+}
+
+impl TryFrom<LevelFile> for Level {
+    type Error = LevelLoaderError;
+
+    fn try_from(file: LevelFile) -> Result<Self, Self::Error> {
+        let mut bricks = Vec::new();
+        let mut columns = 0;
+
+        for (row, line) in file.layout.iter().enumerate() {
+            for (column, tile) in line.chars().enumerate() {
+                let kind = match tile {
+                    '#' | '1' => Some(BrickKind::Normal),
+                    '2'..='9' => tile.to_digit(10).map(|hits| BrickKind::Tough { hits }),
+                    'S' => Some(BrickKind::Steel),
+                    'X' => Some(BrickKind::Explosive),
+                    '.' | ' ' => None,
+                    _ => return Err(LevelLoaderError::UnknownTile { row, column, tile }),
+                };
+                if let Some(kind) = kind {
+                    bricks.push(LevelBrick { row, column, kind });
+                }
+                columns = columns.max(column + 1);
+            }
+        }
+
+        if !bricks.iter().any(|brick| brick.kind.is_destructible()) {
+            return Err(LevelLoaderError::NoBricks);
+        }
+
+        Ok(Level {
+            name: file.name,
+            brick_size: Vec2::new(file.brick_size.0, file.brick_size.1),
+            gap: file.gap,
+            columns,
+            bricks,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    type Asset = Level;
+    type Settings = ();
+    type Error = LevelLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: LevelFile = ron::de::from_bytes(&bytes)?;
+        Level::try_from(file)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+#[derive(Deserialize)]
+struct CampaignFile {
+    levels: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct CampaignLoader;
+
+impl AssetLoader for CampaignLoader {
+    type Asset = Campaign;
+    type Settings = ();
+    type Error = LevelLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: CampaignFile = ron::de::from_bytes(&bytes)?;
+        if file.levels.is_empty() {
+            return Err(LevelLoaderError::EmptyCampaign);
+        }
+
+        let levels = file.levels.into_iter().map(|path| load_context.load(path)).collect();
+        Ok(Campaign { levels })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["campaign.ron"]
+    }
+}
+
+#[derive(Debug)]
+pub enum LevelLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    UnknownTile { row: usize, column: usize, tile: char },
+    NoBricks,
+    EmptyCampaign,
+}
+
+impl fmt::Display for LevelLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelLoaderError::Io(err) => write!(f, "could not read level file: {err}"),
+            LevelLoaderError::Ron(err) => write!(f, "could not parse level file: {err}"),
+            LevelLoaderError::UnknownTile { row, column, tile } => {
+                write!(f, "unknown tile {tile:?} at row {row}, column {column}")
+            }
+            LevelLoaderError::NoBricks => write!(f, "level layout contains no breakable bricks"),
+            LevelLoaderError::EmptyCampaign => write!(f, "campaign contains no levels"),
+        }
+    }
+}
+
+impl std::error::Error for LevelLoaderError {}
+
+impl From<std::io::Error> for LevelLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        LevelLoaderError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for LevelLoaderError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        LevelLoaderError::Ron(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(layout: &[&str]) -> Result<Level, LevelLoaderError> {
+        Level::try_from(LevelFile {
+            name: "Test".to_string(),
+            brick_size: default_brick_size(),
+            gap: default_gap(),
+            layout: layout.iter().map(|row| row.to_string()).collect(),
+        })
+    }
+
+    #[test]
+    fn unknown_tile_reports_where_it_is() {
+        let err = parse(&["###", "#?#"]).unwrap_err();
+        assert!(matches!(err, LevelLoaderError::UnknownTile { row: 1, column: 1, tile: '?' }));
+    }
+
+    #[test]
+    fn layout_without_breakable_bricks_is_rejected() {
+        assert!(matches!(parse(&["...", "   "]), Err(LevelLoaderError::NoBricks)));
+        // steel never breaks, so it cannot be cleared either
+        assert!(matches!(parse(&["SSS"]), Err(LevelLoaderError::NoBricks)));
+    }
+
+    #[test]
+    fn layout_wider_than_the_arena_does_not_fit() {
+        let config = GameConfig::default();
+        assert!(parse(&["########"]).unwrap().fits(&config));
+        assert!(!parse(&["#########"]).unwrap().fits(&config));
+    }
+
+    #[test]
+    fn shipped_levels_fit_the_default_arena() {
+        let config = GameConfig::default();
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/levels");
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if !path.to_string_lossy().ends_with(".level.ron") {
+                continue;
+            }
+            let file: LevelFile = ron::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            let level = Level::try_from(file).unwrap();
+            assert!(level.fits(&config), "{} reaches past the walls: {:?}", path.display(), level.bounds(&config));
+        }
+    }
+}
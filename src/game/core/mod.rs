@@ -1,4 +1,14 @@
-pub(crate) mod components;
+pub mod components;
 pub(crate) mod constants;
-pub(crate) mod resources;
-pub(crate) mod events;
\ No newline at end of file
+pub mod resources;
+pub mod events;
+pub mod collision;
+pub mod level;
+pub mod states;
+pub mod high_scores;
+pub mod config;
+pub mod persistence;
+
+pub mod actions;
+pub mod replay;
+pub mod broadphase;
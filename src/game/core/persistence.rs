@@ -0,0 +1,81 @@
+use std::{fmt, fs, io, path::{Path, PathBuf}};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::game::prelude::*;
+
+// `file` in the game's folder under the user's config directory, or the working directory without one.
+pub fn config_path(file: &str) -> PathBuf {
+    dirs::config_dir().unwrap_or_else(|| PathBuf::from(".")).join(APP_DATA_DIR).join(file)
+}
+
+// `file` in the game's folder under the user's data directory, or the working directory without one.
+pub fn data_path(file: &str) -> PathBuf {
+    dirs::data_dir().unwrap_or_else(|| PathBuf::from(".")).join(APP_DATA_DIR).join(file)
+}
+
+// `None` when there is no file at `path`.
+pub fn load_ron<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, RonFileError> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(ron::from_str(&text)?)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+// For files players may want to read or edit by hand.
+pub fn save_ron<T: Serialize>(path: &Path, value: &T) -> Result<(), RonFileError> {
+    write_replacing(path, &ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())?)
+}
+
+pub fn save_ron_compact<T: Serialize>(path: &Path, value: &T) -> Result<(), RonFileError> {
+    write_replacing(path, &ron::to_string(value)?)
+}
+
+// Written to a temporary file first so a crash cannot leave a half-written file behind.
+fn write_replacing(path: &Path, text: &str) -> Result<(), RonFileError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp_path = path.with_extension("ron.tmp");
+    fs::write(&temp_path, text)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum RonFileError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Write(ron::Error),
+}
+
+impl fmt::Display for RonFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RonFileError::Io(err) => write!(f, "could not access file: {err}"),
+            RonFileError::Parse(err) => write!(f, "could not parse file: {err}"),
+            RonFileError::Write(err) => write!(f, "could not serialize: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RonFileError {}
+
+impl From<io::Error> for RonFileError {
+    fn from(err: io::Error) -> Self {
+        RonFileError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for RonFileError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        RonFileError::Parse(err)
+    }
+}
+
+impl From<ron::Error> for RonFileError {
+    fn from(err: ron::Error) -> Self {
+        RonFileError::Write(err)
+    }
+}
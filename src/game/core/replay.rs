@@ -0,0 +1,118 @@
+use std::{io, path::{Path, PathBuf}};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::prelude::*;
+
+// What `PaddleInput` held when one physics tick ran.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub struct TickInput {
+    pub direction: f32,
+    pub target_x: Option<f32>,
+    pub serve: bool,
+}
+
+impl TickInput {
+    pub fn read(paddle_input: &PaddleInput) -> Self {
+        Self { direction: paddle_input.direction, target_x: paddle_input.target_x, serve: paddle_input.serve }
+    }
+
+    pub fn apply(&self, paddle_input: &mut PaddleInput) {
+        paddle_input.direction = self.direction;
+        paddle_input.target_x = self.target_x;
+        paddle_input.serve = self.serve;
+    }
+}
+
+/// One attempt at a stage: everything the simulation starts from, then the paddle input of every
+/// physics tick. Feeding the ticks back from the same start reproduces the attempt exactly.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Replay {
+    // 0-based stage in the campaign
+    pub level: usize,
+    // `PowerUpRng` seed when the stage started
+    pub seed: u64,
+    pub lives: usize,
+    pub score: usize,
+    pub config: GameConfig,
+    // runs of identical ticks as (count, input); the paddle input rarely changes from one tick to the next
+    ticks: Vec<(u32, TickInput)>,
+}
+
+impl Replay {
+    pub fn new(level: usize, seed: u64, lives: usize, score: usize, config: GameConfig) -> Self {
+        Self { level, seed, lives, score, config, ticks: Vec::new() }
+    }
+
+    pub fn push(&mut self, input: TickInput) {
+        match self.ticks.last_mut() {
+            Some((count, last)) if *last == input => *count += 1,
+            _ => self.ticks.push((1, input)),
+        }
+    }
+
+    pub fn tick_count(&self) -> usize {
+        self.ticks.iter().map(|(count, _)| *count as usize).sum()
+    }
+
+    // unlike the other files, a missing replay is an error since there is nothing to play
+    pub fn load(path: &Path) -> Result<Self, RonFileError> {
+        load_ron(path)?.ok_or_else(|| io::Error::from(io::ErrorKind::NotFound).into())
+    }
+
+    // not pretty-printed, a replay holds thousands of ticks
+    pub fn save(&self, path: &Path) -> Result<(), RonFileError> {
+        save_ron_compact(path, self)
+    }
+}
+
+// Where the last stage played is recorded, and what the menu plays back.
+#[derive(Resource, Deref)]
+pub struct ReplayFile(pub PathBuf);
+
+impl Default for ReplayFile {
+    fn default() -> Self {
+        Self(data_path(REPLAY_FILE))
+    }
+}
+
+// The attempt being recorded, from entering the stage until leaving it.
+#[derive(Resource, Deref, DerefMut)]
+pub struct ReplayRecording(pub Replay);
+
+// A replay being fed into the physics ticks in place of the player's input.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    // position in `replay.ticks`, and ticks already used from that run
+    run: usize,
+    used: u32,
+    // restored when the playback ends
+    player_config: GameConfig,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay, player_config: GameConfig) -> Self {
+        Self { replay, run: 0, used: 0, player_config }
+    }
+
+    pub fn player_config(&self) -> &GameConfig {
+        &self.player_config
+    }
+
+    // `None` once every recorded tick has been played
+    pub fn next_tick(&mut self) -> Option<TickInput> {
+        let (count, input) = *self.replay.ticks.get(self.run)?;
+        self.used += 1;
+        if self.used >= count {
+            self.run += 1;
+            self.used = 0;
+        }
+        Some(input)
+    }
+}
+
+// A replay handed to `GamePlugin` or asked for from the menu, started once the campaign has loaded.
+#[derive(Resource, Deref)]
+pub struct PendingReplay(pub PathBuf);
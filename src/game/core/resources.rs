@@ -1,10 +1,11 @@
 pub use bevy::prelude::*;
-
-#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
-pub enum GameState {
-    Menu,
-    Playing,
-}
+use crate::game::core::{
+    components::PowerUpKind,
+    config::GameConfig,
+    events::BallCollided,
+    constants::{CAMPAIGN_PATH, COMBO_HITS_PER_MULTIPLIER, MAX_COMBO_MULTIPLIER, SLOW_BALL_FACTOR, WIDE_PADDLE_FACTOR},
+    level::{Campaign, Level},
+};
 
 #[derive(Resource, Deref)]
 pub struct CollisionSound(Handle<AudioSource>);
@@ -15,7 +16,7 @@ impl CollisionSound {
     }
 }
 
-#[derive(Resource, Deref, DerefMut)]
+#[derive(Resource, Default, Deref, DerefMut)]
 pub struct Score(usize);
 
 #[allow(dead_code)]
@@ -35,4 +36,313 @@ impl Score {
     pub fn get(&self) -> usize {
         self.0
     }
-}
\ No newline at end of file
+}
+#[derive(Resource, Deref, DerefMut)]
+pub struct Lives(usize);
+
+// a fresh game's lives, from the config
+impl FromWorld for Lives {
+    fn from_world(world: &mut World) -> Self {
+        Self(world.get_resource_or_init::<GameConfig>().starting_lives)
+    }
+}
+
+impl Lives {
+    pub fn new(value: usize) -> Self {
+        Self(value)
+    }
+
+    pub fn get(&self) -> usize {
+        self.0
+    }
+
+    // returns true when this was the last life
+    pub fn lose_one(&mut self) -> bool {
+        self.0 = self.0.saturating_sub(1);
+        self.0 == 0
+    }
+
+    pub fn gain_one(&mut self) {
+        self.0 += 1;
+    }
+}
+
+// Breakable bricks still standing, counted as bricks spawn and despawn.
+#[derive(Resource, Default)]
+pub struct RemainingBricks(usize);
+
+impl RemainingBricks {
+    pub fn get(&self) -> usize {
+        self.0
+    }
+
+    pub fn add_one(&mut self) {
+        self.0 += 1;
+    }
+
+    pub fn remove_one(&mut self) {
+        self.0 = self.0.saturating_sub(1);
+    }
+}
+
+// Score, lives and power-up draws as they were when the stage on screen began.
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct StageStart {
+    pub score: usize,
+    pub lives: usize,
+    pub seed: u64,
+}
+
+// Bricks hit since a ball last touched the paddle.
+#[derive(Resource, Default)]
+pub struct Combo(usize);
+
+impl Combo {
+    pub fn get(&self) -> usize {
+        self.0
+    }
+
+    pub fn hit(&mut self) {
+        self.0 += 1;
+    }
+
+    pub fn reset(&mut self) {
+        self.0 = 0;
+    }
+
+    pub fn multiplier(&self) -> usize {
+        (1 + self.0 / COMBO_HITS_PER_MULTIPLIER).min(MAX_COMBO_MULTIPLIER)
+    }
+}
+
+// Name being typed for a run that made the high-score table.
+#[derive(Resource, Default)]
+pub struct NameEntry {
+    pub name: String,
+    pub active: bool,
+}
+
+// Timed power-up effects that are running, each with the time it has left.
+#[derive(Resource, Default)]
+pub struct ActivePowerUps {
+    active: Vec<(PowerUpKind, Timer)>,
+}
+
+impl ActivePowerUps {
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.active.iter().any(|(active, _)| *active == kind)
+    }
+
+    // catching an effect that is already running restarts its timer
+    pub fn activate(&mut self, kind: PowerUpKind, duration: f32) {
+        let timer = Timer::from_seconds(duration, TimerMode::Once);
+        match self.active.iter_mut().find(|(active, _)| *active == kind) {
+            Some((_, running)) => *running = timer,
+            None => self.active.push((kind, timer)),
+        }
+    }
+
+    // returns the effects that ran out
+    pub fn tick(&mut self, delta: std::time::Duration) -> Vec<PowerUpKind> {
+        let mut expired = Vec::new();
+        self.active.retain_mut(|(kind, timer)| {
+            let finished = timer.tick(delta).is_finished();
+            if finished {
+                expired.push(*kind);
+            }
+            !finished
+        });
+        expired
+    }
+
+    pub fn clear(&mut self) {
+        self.active.clear();
+    }
+
+    pub fn paddle_width(&self, config: &GameConfig) -> f32 {
+        if self.is_active(PowerUpKind::WidePaddle) {
+            config.paddle_size.x * WIDE_PADDLE_FACTOR
+        } else {
+            config.paddle_size.x
+        }
+    }
+
+    pub fn ball_speed(&self, config: &GameConfig) -> f32 {
+        if self.is_active(PowerUpKind::SlowBall) {
+            config.ball_speed * SLOW_BALL_FACTOR
+        } else {
+            config.ball_speed
+        }
+    }
+}
+
+// Xorshift generator for power-up drops, so a run with the same seed drops the same capsules.
+#[derive(Resource)]
+pub struct PowerUpRng(u64);
+
+impl PowerUpRng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift never leaves zero
+        Self(seed.max(1))
+    }
+
+    // a generator made from this seed continues the same sequence
+    pub fn seed(&self) -> u64 {
+        self.0
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn chance(&mut self, probability: f32) -> bool {
+        ((self.next_u64() >> 40) as f32 / (1u64 << 24) as f32) < probability
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[(self.next_u64() % items.len() as u64) as usize]
+    }
+}
+
+#[derive(Resource)]
+pub struct CurrentLevel {
+    campaign: Handle<Campaign>,
+    index: usize,
+}
+
+// Created while the plugins are built so that it already exists for the first `OnEnter(GameState::Menu)`,
+// which runs before `Startup`.
+impl FromWorld for CurrentLevel {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(world.resource::<AssetServer>().load(CAMPAIGN_PATH))
+    }
+}
+
+impl CurrentLevel {
+    pub fn new(campaign: Handle<Campaign>) -> Self {
+        Self { campaign, index: 0 }
+    }
+
+    pub fn campaign(&self) -> &Handle<Campaign> {
+        &self.campaign
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn level<'a>(&self, campaigns: &Assets<Campaign>, levels: &'a Assets<Level>) -> Option<&'a Level> {
+        let handle = campaigns.get(&self.campaign)?.levels.get(self.index)?;
+        levels.get(handle)
+    }
+
+    pub fn is_last(&self, campaigns: &Assets<Campaign>) -> bool {
+        campaigns
+            .get(&self.campaign)
+            .is_none_or(|campaign| self.index + 1 >= campaign.levels.len())
+    }
+
+    pub fn advance(&mut self) {
+        self.index += 1;
+    }
+
+    // back to the first stage of the campaign
+    pub fn restart(&mut self) {
+        self.index = 0;
+    }
+
+    pub fn jump_to(&mut self, index: usize) {
+        self.index = index;
+    }
+}
+
+// index into `PauseMenuOption::ALL`
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct PauseMenuSelection(usize);
+
+// Row of the controls screen, and whether it waits for an input to bind to that row's action.
+#[derive(Resource, Default)]
+pub struct SettingsSelection {
+    pub row: usize,
+    pub listening: bool,
+}
+
+// Player input sampled every frame and consumed by the fixed physics ticks,
+// so a press is neither lost nor applied twice when a frame runs zero or several ticks.
+#[derive(Resource, Default)]
+pub struct PaddleInput {
+    pub direction: f32,
+    // world x the paddle heads for in pointer mode, `None` while no cursor or finger is over the window
+    pub target_x: Option<f32>,
+    pub serve: bool,
+}
+
+// Whether the collider debug overlay is up, and the most recent ball contact it shows.
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub enabled: bool,
+    pub last_contact: Option<BallCollided>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn combo_multiplier_climbs_every_few_hits_up_to_its_cap() {
+        let mut combo = Combo::default();
+        assert_eq!(combo.multiplier(), 1);
+
+        for _ in 0..COMBO_HITS_PER_MULTIPLIER - 1 {
+            combo.hit();
+        }
+        assert_eq!(combo.multiplier(), 1);
+        combo.hit();
+        assert_eq!(combo.multiplier(), 2);
+
+        for _ in 0..COMBO_HITS_PER_MULTIPLIER * MAX_COMBO_MULTIPLIER * 2 {
+            combo.hit();
+        }
+        assert_eq!(combo.multiplier(), MAX_COMBO_MULTIPLIER);
+
+        combo.reset();
+        assert_eq!(combo.multiplier(), 1);
+    }
+
+    #[test]
+    fn catching_a_running_power_up_restarts_its_timer() {
+        let mut active = ActivePowerUps::default();
+        active.activate(PowerUpKind::WidePaddle, 10.0);
+        assert!(active.tick(Duration::from_secs(8)).is_empty());
+
+        active.activate(PowerUpKind::WidePaddle, 10.0);
+        assert!(active.tick(Duration::from_secs(8)).is_empty());
+        assert!(active.is_active(PowerUpKind::WidePaddle));
+
+        assert_eq!(active.tick(Duration::from_secs(2)), [PowerUpKind::WidePaddle]);
+        assert!(!active.is_active(PowerUpKind::WidePaddle));
+    }
+
+    #[test]
+    fn expired_power_ups_give_back_the_configured_sizes() {
+        let config = GameConfig::default();
+        let mut active = ActivePowerUps::default();
+        active.activate(PowerUpKind::WidePaddle, 5.0);
+        active.activate(PowerUpKind::SlowBall, 10.0);
+        assert_eq!(active.paddle_width(&config), config.paddle_size.x * WIDE_PADDLE_FACTOR);
+        assert_eq!(active.ball_speed(&config), config.ball_speed * SLOW_BALL_FACTOR);
+
+        // each effect runs out on its own
+        assert_eq!(active.tick(Duration::from_secs(5)), [PowerUpKind::WidePaddle]);
+        assert_eq!(active.paddle_width(&config), config.paddle_size.x);
+        assert_eq!(active.ball_speed(&config), config.ball_speed * SLOW_BALL_FACTOR);
+
+        assert_eq!(active.tick(Duration::from_secs(5)), [PowerUpKind::SlowBall]);
+        assert_eq!(active.ball_speed(&config), config.ball_speed);
+    }
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+#[derive(States, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameState {
+    #[default]
+    Menu,
+    Playing,
+    Paused,
+    GameOver,
+    StageClear,
+    // the controls screen, reached from the menu
+    Settings,
+}
+
+// Active while a level is on screen. Level entities are scoped to it so that
+// pausing keeps them alive, while leaving the run despawns them.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = GameState;
+
+    fn compute(sources: GameState) -> Option<Self> {
+        matches!(sources, GameState::Playing | GameState::Paused).then_some(InGame)
+    }
+}
@@ -1,19 +1,58 @@
 pub mod prelude;
 
-mod core;
-mod plugins;
-mod systems;
+pub(crate) mod core;
+pub mod plugins;
+pub(crate) mod systems;
+
+use std::path::PathBuf;
 
 pub(crate) use prelude::*;
-pub struct GamePlugin;
+
+/// The whole game. `GamePlugin::default()` reads the player's settings file and starts at the menu;
+/// the builder methods hand it settings or a replay instead, e.g. parsed from a command line.
+#[derive(Default)]
+pub struct GamePlugin {
+    config: Option<GameConfig>,
+    replay: Option<PathBuf>,
+}
+
+impl GamePlugin {
+    pub fn with_config(mut self, config: GameConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    // played back as soon as the campaign has loaded
+    pub fn with_replay(mut self, path: impl Into<PathBuf>) -> Self {
+        self.replay = Some(path.into());
+        self
+    }
+}
+
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(plugins::core::CorePlugin)
+        if let Some(config) = &self.config {
+            app.insert_resource(config.clone());
+        }
+        if let Some(path) = &self.replay {
+            app.insert_resource(PendingReplay(path.clone()));
+        }
+        app.add_plugins(plugins::config::ConfigPlugin)
+            .add_plugins(plugins::core::CorePlugin)
             .add_plugins(plugins::start::StartPlugin)
             .add_plugins(plugins::input::InputPlugin)
             .add_plugins(plugins::physics::PhysicsPlugin)
             .add_plugins(plugins::ui::ScoreboardPlugin)
             .add_plugins(plugins::audio::AudioPlugin)
-            .add_plugins(plugins::resource::ResourcePlugin);
+            .add_plugins(plugins::resource::ResourcePlugin)
+            .add_plugins(plugins::level::LevelPlugin)
+            .add_plugins(plugins::overlay::OverlayPlugin)
+            .add_plugins(plugins::pause::PausePlugin)
+            .add_plugins(plugins::power_up::PowerUpPlugin)
+            .add_plugins(plugins::high_score::HighScorePlugin)
+            .add_plugins(plugins::combo::ComboPlugin)
+            .add_plugins(plugins::settings::SettingsPlugin)
+            .add_plugins(plugins::replay::ReplayPlugin)
+            .add_plugins(plugins::debug::DebugPlugin);
     }
 }
\ No newline at end of file
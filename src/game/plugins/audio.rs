@@ -5,10 +5,24 @@ pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(play_collision_sound);
+        // headless runs leave bevy's audio out, and with it the `AudioSource` asset
+        if !app.is_plugin_added::<bevy::audio::AudioPlugin>() {
+            return;
+        }
+        app.add_systems(Startup, load_collision_sound)
+            .add_observer(play_collision_sound);
     }
 }
 
+fn load_collision_sound(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    // Preload collision sound resource so it's available when level spawns
+    let ball_collision_sound = asset_server.load("sounds/breakout_collision.ogg");
+    commands.insert_resource(CollisionSound::new(ball_collision_sound));
+}
+
 fn play_collision_sound(
     _collided: On<BallCollided>,
     mut commands: Commands,
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use crate::game::prelude::*;
+
+pub struct ComboPlugin;
+
+impl Plugin for ComboPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Combo>()
+            .add_observer(spawn_score_popup)
+            .add_systems(OnExit(InGame), reset_combo)
+            .add_systems(Update, animate_score_popups.run_if(in_state(InGame)));
+    }
+}
+
+fn spawn_score_popup(destroyed: On<BrickDestroyed>, mut commands: Commands, config: Res<GameConfig>) {
+    commands.spawn((
+        Text2d::new(format!("+{} x{}", destroyed.points, destroyed.multiplier)),
+        TextFont {
+            font_size: SCORE_POPUP_FONT_SIZE,
+            ..default()
+        },
+        TextColor(config.score_color),
+        Transform::from_translation(destroyed.position.extend(2.0)),
+        ScorePopup(Timer::from_seconds(SCORE_POPUP_DURATION, TimerMode::Once)),
+        DespawnOnExit(InGame),
+    ));
+}
+
+// popups rise and fade out, and stay put while the game is paused
+fn animate_score_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut TextColor)>,
+    config: Res<GameConfig>,
+) {
+    for (entity, mut popup, mut transform, mut color) in &mut query {
+        if popup.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation.y += SCORE_POPUP_RISE_SPEED * time.delta_secs();
+        color.0 = config.score_color.with_alpha(popup.fraction_remaining());
+    }
+}
+
+// each stage starts without a combo
+fn reset_combo(mut combo: ResMut<Combo>) {
+    combo.reset();
+}
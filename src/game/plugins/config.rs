@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use crate::game::prelude::*;
+
+// Added before the other game plugins, some of which read the config while they are built.
+pub struct ConfigPlugin;
+
+impl Plugin for ConfigPlugin {
+    fn build(&self, app: &mut App) {
+        // a config handed to `GamePlugin` or inserted up front wins over the settings file
+        if !app.world().contains_resource::<GameConfig>() {
+            app.insert_resource(GameConfig::from_file(&GameConfig::default_path()));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, gizmos::GizmoPlugin, prelude::*};
+use crate::game::{prelude::*, systems::ui::debug_ui};
+
+/// Toggled with the `DebugOverlay` action (F3): draws every collider, each ball with its velocity
+/// and the last contact with its normal using gizmos, next to a readout of frame rate, entity
+/// counts and paddle cooldowns.
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        // headless runs have nothing to draw with
+        if !app.is_plugin_added::<GizmoPlugin>() {
+            return;
+        }
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+
+        app.init_resource::<DebugOverlay>()
+            .add_observer(record_contact)
+            .add_systems(Startup, debug_ui::spawn_debug_readout)
+            .add_systems(Update, toggle_debug_overlay)
+            .add_systems(Update, (
+                draw_colliders,
+                draw_balls,
+                draw_last_contact,
+            ).run_if(debug_overlay_enabled.and(in_state(InGame))))
+            .add_systems(Update, debug_ui::update_debug_readout
+                .after(toggle_debug_overlay)
+                .run_if(debug_overlay_enabled.or(resource_changed::<DebugOverlay>)));
+    }
+}
+
+fn debug_overlay_enabled(overlay: Res<DebugOverlay>) -> bool {
+    overlay.enabled
+}
+
+fn toggle_debug_overlay(
+    actions: Res<ActionState>,
+    mut overlay: ResMut<DebugOverlay>,
+) {
+    if actions.just_pressed(Action::DebugOverlay) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
+fn record_contact(collided: On<BallCollided>, mut overlay: ResMut<DebugOverlay>) {
+    overlay.last_contact = Some(*collided);
+}
+
+fn draw_colliders(
+    mut gizmos: Gizmos,
+    colliders: Query<&Transform, With<Collider>>,
+) {
+    for transform in &colliders {
+        gizmos.rect_2d(transform.translation.truncate(), transform.scale.truncate(), DEBUG_COLLIDER_COLOR);
+    }
+}
+
+fn draw_balls(
+    mut gizmos: Gizmos,
+    balls: Query<(&Transform, &Velocity), With<Ball>>,
+    config: Res<GameConfig>,
+) {
+    for (transform, velocity) in &balls {
+        let center = transform.translation.truncate();
+        gizmos.circle_2d(center, config.ball_diameter / 2.0, DEBUG_BALL_COLOR);
+        gizmos.arrow_2d(center, center + **velocity * DEBUG_VELOCITY_SCALE, DEBUG_BALL_COLOR);
+    }
+}
+
+fn draw_last_contact(
+    mut gizmos: Gizmos,
+    overlay: Res<DebugOverlay>,
+) {
+    let Some(contact) = overlay.last_contact else { return; };
+    gizmos.circle_2d(contact.point, 3.0, DEBUG_CONTACT_COLOR);
+    gizmos.arrow_2d(contact.point, contact.point + contact.normal * DEBUG_NORMAL_LENGTH, DEBUG_CONTACT_COLOR);
+}
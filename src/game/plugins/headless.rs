@@ -0,0 +1,18 @@
+use bevy::{input::InputPlugin, prelude::*, state::app::StatesPlugin, text::TextIterScratch};
+
+/// The parts of `DefaultPlugins` that `GamePlugin` cannot run without, for use with `MinimalPlugins`.
+///
+/// Nothing here needs a window, a GPU or an audio device: sprites, meshes and text are still
+/// spawned but never drawn, and the collision sound is skipped because bevy's audio is absent.
+/// Input comes from `KeyboardInput` messages written by the caller.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((AssetPlugin::default(), StatesPlugin, InputPlugin))
+            .init_asset::<Mesh>()
+            .init_asset::<ColorMaterial>()
+            // needed by `TextUiWriter`, normally added by the text plugin
+            .init_resource::<TextIterScratch>();
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::{input::{keyboard::{Key, KeyboardInput}, ButtonState}, prelude::*};
+use crate::game::{prelude::*, systems::ui::high_score_ui};
+
+pub struct HighScorePlugin;
+
+impl Plugin for HighScorePlugin {
+    fn build(&self, app: &mut App) {
+        // the file resource goes first so the table loads from it
+        app.init_resource::<HighScoreFile>()
+            .init_resource::<HighScores>()
+            .init_resource::<NameEntry>()
+            // a played-back run is not the player's own
+            .add_systems(OnEnter(GameState::GameOver), begin_name_entry.run_if(not(resource_exists::<ReplayPlayback>)))
+            .add_systems(OnEnter(GameState::StageClear), begin_name_entry.run_if(not(resource_exists::<ReplayPlayback>)))
+            .add_systems(Update, (
+                type_name.run_if(name_entry_active),
+                high_score_ui::update_name_entry_ui.run_if(resource_changed::<NameEntry>),
+            ).chain().run_if(in_state(GameState::GameOver).or(in_state(GameState::StageClear))));
+    }
+}
+
+pub(crate) fn name_entry_active(name_entry: Res<NameEntry>) -> bool {
+    name_entry.active
+}
+
+// Runs when the game ends, either out of lives or after the last stage.
+fn begin_name_entry(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    score: Res<Score>,
+    high_scores: Res<HighScores>,
+    current_level: Res<CurrentLevel>,
+    campaigns: Res<Assets<Campaign>>,
+    mut name_entry: ResMut<NameEntry>,
+    config: Res<GameConfig>,
+) {
+    if *state.get() == GameState::StageClear && !current_level.is_last(&campaigns) {
+        return;
+    }
+    if !high_scores.qualifies(score.get()) {
+        return;
+    }
+    name_entry.name.clear();
+    name_entry.active = true;
+    high_score_ui::spawn_name_entry_ui(&mut commands, *state.get(), &config);
+}
+
+// Enter or any Confirm input (e.g. a gamepad's South button) saves the entry, so a player
+// without a keyboard gets the default name instead of being stuck on the overlay.
+fn type_name(
+    mut keyboard_events: MessageReader<KeyboardInput>,
+    mut actions: ResMut<ActionState>,
+    mut name_entry: ResMut<NameEntry>,
+    mut high_scores: ResMut<HighScores>,
+    high_score_file: Res<HighScoreFile>,
+    score: Res<Score>,
+    current_level: Res<CurrentLevel>,
+) {
+    let mut confirmed = actions.just_pressed(Action::Confirm);
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Backspace => {
+                name_entry.name.pop();
+            }
+            Key::Enter => {
+                confirmed = true;
+                break;
+            }
+            _ => {
+                let typed = event.text.iter().flat_map(|text| text.chars()).filter(char::is_ascii_alphanumeric);
+                for c in typed {
+                    if name_entry.name.len() < HIGH_SCORE_NAME_MAX_LEN {
+                        name_entry.name.push(c.to_ascii_uppercase());
+                    }
+                }
+            }
+        }
+    }
+    if !confirmed {
+        return;
+    }
+
+    let name = if name_entry.name.is_empty() {
+        HIGH_SCORE_DEFAULT_NAME.to_string()
+    } else {
+        name_entry.name.clone()
+    };
+    let entry = HighScoreEntry {
+        name,
+        score: score.get(),
+        level: current_level.index() + 1,
+        date: today(),
+    };
+    if let Some(rank) = high_scores.insert(entry) {
+        info!("High score saved at rank {}", rank + 1);
+    }
+    if let Err(err) = high_scores.save(&high_score_file) {
+        warn!("Could not save high scores to {}: {err}", high_score_file.display());
+    }
+    name_entry.active = false;
+    // the same press must not also leave the overlay
+    actions.clear_just_pressed(Action::Confirm);
+}
@@ -1,55 +1,67 @@
-use bevy::prelude::*;
-use crate::game::{prelude::*, systems::ui::start_ui};
+use bevy::{input::InputSystems, prelude::*};
+use crate::game::{prelude::*, plugins::{high_score::name_entry_active, level::level_ready}};
 
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, input_start_game)
-            .add_systems(Update, input_return_to_menu);
+        // the file resource goes first so the bindings load from it
+        app.init_resource::<InputBindingsFile>()
+            .init_resource::<InputBindings>()
+            .init_resource::<ActionState>()
+            .add_systems(PreUpdate, update_action_state.after(InputSystems))
+            .add_systems(Update, input_start_game.run_if(in_state(GameState::Menu).and(level_ready)))
+            .add_systems(Update, input_open_settings.run_if(in_state(GameState::Menu)))
+            .add_systems(Update, input_continue.run_if(
+                in_state(GameState::StageClear).or(in_state(GameState::GameOver)).and(not(name_entry_active))
+            ));
     }
 }
 
-fn input_start_game(
+// Gamepads are queried every frame, so controllers plugged in mid-game work right away.
+fn update_action_state(
     keys: Res<ButtonInput<KeyCode>>,
-    mut state: ResMut<GameState>,
-    mut commands: Commands,
-    start_ui: Query<Entity, With<StartUi>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<InputBindings>,
+    mut actions: ResMut<ActionState>,
+) {
+    actions.update(&bindings, &keys, &mouse_buttons, &gamepads);
+}
+
+fn input_start_game(
+    actions: Res<ActionState>,
+    mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if *state == GameState::Menu && (keys.just_pressed(KeyCode::Space ) || keys.just_pressed(KeyCode::Enter)) {
-        // remove any StartUi so it doesn't cover the scene
-        for e in &start_ui {
-            commands.entity(e).despawn();
-        }
-        // just transition to Playing; level already exists in background
-        *state = GameState::Playing;
+    if actions.just_pressed(Action::Confirm) {
+        // the start UI despawns with the menu state and the level spawns on entering the game
+        next_state.set(GameState::Playing);
         info!("Game started");
     }
 }
 
-fn input_return_to_menu(
-    keys: Res<ButtonInput<KeyCode>>,
-    mut state: ResMut<GameState>,
-    mut commands: Commands,
-    mut score: ResMut<Score>,
-    meshes: ResMut<Assets<Mesh>>,
-    materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
-    query: Query<Entity, Or<(With<Paddle>, With<Ball>, With<Brick>, With<Collider>, With<Wall>, With<ScoreboardUi>)>>,
+fn input_open_settings(
+    actions: Res<ActionState>,
+    mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if *state == GameState::Playing && keys.just_pressed(KeyCode::Escape) {
-        // despawn all game entities
-        for e in &query {
-            commands.entity(e).despawn();
-        }
-        // reset score
-        score.set_zero();
-        // insert menu state
-        *state = GameState::Menu;
-        // spawn Start UI overlay
-        start_ui::spawn_start_ui(&mut commands);
-        // respawn level in background (systems disabled because state=Menu)
-        spawn_breakout_ui(commands, meshes, materials, asset_server);
-        info!("Returned to menu and reset game");
+    if actions.just_pressed(Action::Controls) {
+        next_state.set(GameState::Settings);
     }
-}
\ No newline at end of file
+}
+
+fn input_continue(
+    actions: Res<ActionState>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    current_level: Res<CurrentLevel>,
+    campaigns: Res<Assets<Campaign>>,
+) {
+    if !actions.just_pressed(Action::Confirm) { return; }
+
+    let finished = *state.get() == GameState::GameOver || current_level.is_last(&campaigns);
+    if finished {
+        next_state.set(GameState::Menu);
+    } else {
+        next_state.set(GameState::Playing);
+    }
+}
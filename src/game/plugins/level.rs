@@ -0,0 +1,71 @@
+use bevy::{asset::RecursiveDependencyLoadState, prelude::*};
+use crate::game::{prelude::*, systems::ui::start_ui};
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Level>()
+            .init_asset::<Campaign>()
+            .init_asset_loader::<LevelLoader>()
+            .init_asset_loader::<CampaignLoader>()
+            .init_resource::<CurrentLevel>()
+            .init_resource::<StageStart>()
+            .add_systems(OnEnter(InGame), (remember_stage_start, spawn_current_level))
+            .add_systems(Update, show_load_error.run_if(in_state(GameState::Menu)));
+    }
+}
+
+// run condition: the campaign and every level in it have finished loading
+pub(crate) fn level_ready(current_level: Res<CurrentLevel>, asset_server: Res<AssetServer>) -> bool {
+    asset_server.is_loaded_with_dependencies(current_level.campaign())
+}
+
+// A campaign or level that fails to load keeps `level_ready` false for good, so the menu says why
+// instead of ignoring the start input.
+fn show_load_error(
+    mut commands: Commands,
+    current_level: Res<CurrentLevel>,
+    asset_server: Res<AssetServer>,
+    error_ui: Query<(), With<LoadErrorUi>>,
+    config: Res<GameConfig>,
+) {
+    if !error_ui.is_empty() {
+        return;
+    }
+    if let Some(RecursiveDependencyLoadState::Failed(err)) = asset_server.get_recursive_dependency_load_state(current_level.campaign()) {
+        error!("Could not load the campaign: {err}");
+        start_ui::spawn_load_error_ui(&mut commands, format!("Could not load the campaign:\n{err}"), &config);
+    }
+}
+
+// so the pause menu's Restart can go back to it
+fn remember_stage_start(
+    mut stage_start: ResMut<StageStart>,
+    score: Res<Score>,
+    lives: Res<Lives>,
+    power_up_rng: Res<PowerUpRng>,
+) {
+    *stage_start = StageStart { score: score.get(), lives: lives.get(), seed: power_up_rng.seed() };
+}
+
+pub(crate) fn spawn_current_level(
+    commands: Commands,
+    meshes: ResMut<Assets<Mesh>>,
+    materials: ResMut<Assets<ColorMaterial>>,
+    campaigns: Res<Assets<Campaign>>,
+    levels: Res<Assets<Level>>,
+    current_level: Res<CurrentLevel>,
+    config: Res<GameConfig>,
+) {
+    let Some(level) = current_level.level(&campaigns, &levels) else {
+        warn!("Level {} is not loaded, nothing to spawn", current_level.index() + 1);
+        return;
+    };
+
+    if !level.fits(&config) {
+        warn!("Level \"{}\" does not fit between the walls and above the paddle: {:?}", level.name, level.bounds(&config));
+    }
+    spawn_breakout_ui(commands, meshes, materials, level, &config);
+    info!("Spawned level \"{}\"", level.name);
+}
@@ -1,7 +1,18 @@
-pub(crate) mod core;
-pub(crate) mod start;
-pub(crate) mod input;
-pub(crate) mod physics;
-pub(crate) mod ui;
-pub(crate) mod audio;
-pub(crate) mod resource;
\ No newline at end of file
+pub mod core;
+pub mod start;
+pub mod input;
+pub mod physics;
+pub mod ui;
+pub mod audio;
+pub mod resource;
+pub mod level;
+pub mod overlay;
+pub mod pause;
+pub mod power_up;
+pub mod high_score;
+pub mod combo;
+pub mod config;
+pub mod headless;
+pub mod settings;
+pub mod replay;
+pub mod debug;
@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+use crate::game::{prelude::*, systems::ui::{gameover_ui, stageclear_ui}};
+
+pub struct OverlayPlugin;
+
+impl Plugin for OverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::GameOver), gameover_ui::spawn_gameover_ui)
+            .add_systems(OnEnter(GameState::StageClear), stageclear_ui::spawn_stageclear_ui)
+            .add_systems(OnExit(GameState::StageClear), stageclear_ui::advance_level);
+    }
+}
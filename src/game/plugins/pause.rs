@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use crate::game::{
+    prelude::*,
+    plugins::{level::spawn_current_level, replay::begin_recording},
+    systems::ui::pause_ui,
+};
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PauseMenuSelection>()
+            .add_systems(OnEnter(GameState::Paused), setup_pause)
+            .add_systems(OnExit(GameState::Paused), resume_time)
+            .add_systems(Update, input_pause.run_if(in_state(GameState::Playing)))
+            .add_systems(Update, (
+                input_pause_menu,
+                highlight_pause_selection,
+            ).chain().run_if(in_state(GameState::Paused)));
+    }
+}
+
+fn setup_pause(
+    mut commands: Commands,
+    mut selection: ResMut<PauseMenuSelection>,
+    mut time: ResMut<Time<Virtual>>,
+    config: Res<GameConfig>,
+) {
+    // virtual time stops so nothing ticks (e.g. paddle cooldowns) while the overlay is up
+    time.pause();
+    **selection = 0;
+    pause_ui::spawn_pause_ui(&mut commands, &config);
+}
+
+fn resume_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn input_pause(
+    actions: Res<ActionState>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        next_state.set(GameState::Paused);
+        info!("Game paused");
+    }
+}
+
+fn input_pause_menu(
+    actions: Res<ActionState>,
+    mut selection: ResMut<PauseMenuSelection>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    level_entities: Query<Entity, With<DespawnOnExit<InGame>>>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    // pausing again or going back resumes directly
+    if actions.just_pressed(Action::Pause) || actions.just_pressed(Action::Back) {
+        next_state.set(GameState::Playing);
+        info!("Game resumed");
+        return;
+    }
+
+    let n_options = PauseMenuOption::ALL.len();
+    if actions.just_pressed(Action::MenuUp) {
+        **selection = (**selection + n_options - 1) % n_options;
+    }
+    if actions.just_pressed(Action::MenuDown) {
+        **selection = (**selection + 1) % n_options;
+    }
+
+    if !actions.just_pressed(Action::Confirm) { return; }
+
+    match PauseMenuOption::ALL[**selection] {
+        PauseMenuOption::Resume => {
+            next_state.set(GameState::Playing);
+            info!("Game resumed");
+        }
+        PauseMenuOption::Restart if playback.is_some() => {
+            // a replay cannot be restarted part way, so watching one just ends it
+            next_state.set(GameState::Menu);
+            info!("Replay stopped");
+        }
+        PauseMenuOption::Restart => {
+            // `InGame` stays active across Paused -> Playing, so the level is rebuilt by hand
+            for e in &level_entities {
+                commands.entity(e).despawn();
+            }
+            commands.run_system_cached(restart_stage);
+            commands.run_system_cached(spawn_current_level);
+            commands.run_system_cached(begin_recording);
+            next_state.set(GameState::Playing);
+            info!("Stage restarted");
+        }
+        PauseMenuOption::QuitToMenu => {
+            // game entities despawn on leaving the game and the menu resets the score
+            next_state.set(GameState::Menu);
+            info!("Returned to menu and reset game");
+        }
+    }
+}
+
+// Back to how the stage began, with no combo or power-ups running. The stage itself stays the same.
+fn restart_stage(
+    stage_start: Res<StageStart>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    mut combo: ResMut<Combo>,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+    mut power_up_rng: ResMut<PowerUpRng>,
+) {
+    score.set(stage_start.score);
+    **lives = stage_start.lives;
+    combo.reset();
+    active_power_ups.clear();
+    *power_up_rng = PowerUpRng::new(stage_start.seed);
+}
+
+fn highlight_pause_selection(
+    selection: Res<PauseMenuSelection>,
+    mut options: Query<(&PauseMenuOption, &mut TextColor)>,
+    config: Res<GameConfig>,
+) {
+    for (option, mut color) in &mut options {
+        let selected = PauseMenuOption::ALL[**selection] == *option;
+        color.0 = if selected { config.title_text_color } else { config.text_color };
+    }
+}
@@ -1,86 +1,324 @@
 use bevy::{prelude::*, math::bounding::{Aabb2d, BoundingCircle}};
 
-use crate::game::{prelude::*, systems::ui::gameover_ui};
-use crate::game::systems::physics::{collisions, paddle, time};
+use crate::game::prelude::*;
+use crate::game::systems::physics::{bricks, collisions, paddle, serve, time};
 
 
+/// Runs the simulation in `FixedUpdate` at `GameConfig::physics_tick_rate`.
+///
+/// Every tick advances by the same fixed delta and reads player input only through
+/// `PaddleInput`, which is sampled once per frame before the ticks run. Nothing in the
+/// simulation depends on the frame rate, and power-up drops come from the seeded `PowerUpRng`,
+/// so two runs that feed the same `PaddleInput` into the same ticks produce the same ball path. Balls and the paddle are
+/// drawn interpolated between the last two ticks to stay smooth at any frame rate.
+///
+/// The plugin initialises every game resource its systems read, so it also runs without the rest
+/// of `GamePlugin`. It still needs bevy's state and input plugins, which `DefaultPlugins` and
+/// `HeadlessPlugin` both provide. Add `ConfigPlugin` or insert a `GameConfig` first if the
+/// settings file should be used; otherwise the default config is.
 pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<GameConfig>()
+            .init_resource::<Score>()
+            .init_resource::<Lives>()
+            .init_resource::<Combo>()
+            .init_resource::<ActivePowerUps>()
+            .init_resource::<ActionState>()
+            .init_state::<GameState>();
+        let tick_rate = app.world().resource::<GameConfig>().physics_tick_rate;
         app
-        .add_systems(Update, time::tick_paddle_cooldowns)
-        .add_systems(Update, (
-            time::apply_velocity,
+        .insert_resource(Time::<Fixed>::from_hz(tick_rate))
+        .init_resource::<PaddleInput>()
+        .init_resource::<ColliderGrid>()
+        .init_resource::<RemainingBricks>()
+        .add_observer(add_to_collider_grid)
+        .add_observer(remove_from_collider_grid)
+        .add_observer(count_added_brick)
+        .add_observer(count_removed_brick)
+        .add_systems(RunFixedMainLoop, (
+            time::restore_physics_translations,
+            (paddle::read_paddle_input, paddle::read_pointer_input).chain().run_if(in_state(GameState::Playing)),
+        ).in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop))
+        .add_systems(RunFixedMainLoop, time::interpolate_translations
+            .in_set(RunFixedMainLoopSystems::AfterFixedMainLoop))
+        .add_systems(FixedFirst, time::store_previous_translations)
+        // one fixed order, so the same inputs always give the same ball path
+        .add_systems(FixedUpdate, (
+            time::tick_paddle_cooldowns,
+            time::tick_respawn_countdowns,
             paddle::move_paddle,
-            check_for_collisions
-        ).chain());
+            serve::carry_served_balls,
+            serve::serve_ball,
+            time::apply_velocity,
+            check_for_collisions,
+            check_stage_clear,
+        ).chain().run_if(in_state(GameState::Playing)));
     }
 }
 
-fn check_for_collisions(
+// Moves each ball along its velocity and bounces it off the first colliders on the way, spending
+// the rest of the frame's movement after each bounce so fast balls cannot tunnel. Colliders met
+// at the same moment are all hit but give a single bounce.
+pub(crate) fn check_for_collisions(
     mut commands: Commands,
     mut score: ResMut<Score>,
-    ball_query: Single<(&mut Velocity, &Transform, &mut PaddleCooldown), With<Ball>>,
-    collider_query: Query<(Entity, &Transform, Option<&Brick>, Option<&Paddle>), With<Collider>>,
-    state: ResMut<GameState>,
+    mut ball_query: Query<(Entity, &mut Velocity, &mut Transform, &mut PaddleCooldown), (With<Ball>, Without<OnPaddle>, Without<Collider>)>,
+    all_balls_query: Query<(), With<Ball>>,
+    collider_query: Query<(Entity, &Transform, Has<Brick>, Has<Paddle>), With<Collider>>,
+    paddle_query: Query<Entity, (With<Paddle>, With<Collider>)>,
+    collider_grid: Res<ColliderGrid>,
+    mut next_state: ResMut<NextState<GameState>>,
     bottom_wall_query: Query<(), With<BottomWall>>,
-    all_query: Query<Entity, Or<(With<Paddle>, With<Ball>, With<Brick>, With<Collider>, With<Wall>, With<ScoreboardUi>)>>,
-    meshes: ResMut<Assets<Mesh>>,
-    materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
+    mut brick_query: Query<(Entity, &mut Brick, &mut Sprite, &Transform), Without<Ball>>,
+    mut lives: ResMut<Lives>,
+    mut combo: ResMut<Combo>,
+    active_power_ups: Res<ActivePowerUps>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
 ) {
-    let (mut ball_velocity, ball_transform, mut ball_cooldown) = ball_query.into_inner();
-    if *state != GameState::Playing { return; }
-
-    let mut game_over = false;
-
-    for (collider_entity, collider_transform, maybe_brick, maybe_paddle) in &collider_query {
-        let is_paddle = maybe_paddle.is_some();
-        let collision = collisions::ball_collision(
-            BoundingCircle::new(ball_transform.translation.truncate(), BALL_DIAMETER / 2.0),
-            Aabb2d::new(
-                collider_transform.translation.truncate(),
-                collider_transform.scale.truncate() / 2.0
-            ),
-            is_paddle,
-        );
-        if let Some(collision) = collision {
-            commands.trigger(BallCollided);
-
-            if maybe_brick.is_some() {
-                commands.entity(collider_entity).despawn();
-                **score += 1;
-            } else if is_paddle{
-                if ball_cooldown.0 > 0.0 {
+    let mut lost_balls = Vec::new();
+    let mut destroyed = Vec::new();
+
+    for (ball_entity, mut ball_velocity, mut ball_transform, mut ball_cooldown) in &mut ball_query {
+        let mut ball_center = ball_transform.translation.truncate();
+        let mut remaining_secs = time.delta_secs();
+
+        for _ in 0..MAX_COLLISIONS_PER_FRAME {
+            let motion = **ball_velocity * remaining_secs;
+
+            // every contact made first along the remaining motion, as (contact, collider, box, is_brick, is_paddle)
+            let slop = CONTACT_SLOP / motion.length().max(f32::EPSILON);
+            let mut earliest_time = f32::INFINITY;
+            let mut hits: Vec<(collisions::Contact, Entity, Aabb2d, bool, bool)> = Vec::new();
+            // only the walls and bricks around the ball's path, plus the paddle
+            let nearby = collider_grid.query(swept_area(ball_center, config.ball_diameter / 2.0, motion));
+            for (collider_entity, collider_transform, is_brick, is_paddle) in collider_query.iter_many(nearby.into_iter().chain(&paddle_query)) {
+                if destroyed.contains(&collider_entity) || (is_paddle && ball_cooldown.0 > 0) {
                     continue;
-                } else {
-                    collisions::ball_reflection(&mut ball_velocity, collision, is_paddle);
-                    ball_cooldown.0 = PADDLE_HIT_COOLDOWN;
+                }
+                let collider_box = Aabb2d::new(
+                    collider_transform.translation.truncate(),
+                    collider_transform.scale.truncate() / 2.0
+                );
+                let Some((hit_time, contact)) = collisions::swept_ball_collision(
+                    BoundingCircle::new(ball_center, config.ball_diameter / 2.0),
+                    motion,
+                    collider_box,
+                    is_paddle,
+                ) else {
+                    continue;
+                };
+                if hit_time > earliest_time + slop {
                     continue;
                 }
-            } else {
-                // not a brick: possibly a wall. If it's the bottom wall, trigger game over
-                if bottom_wall_query.get(collider_entity).is_ok() {
-                    game_over = true;
+                if hit_time < earliest_time - slop {
+                    hits.clear();
+                }
+                earliest_time = earliest_time.min(hit_time);
+                hits.push((contact, collider_entity, collider_box, is_brick, is_paddle));
+            }
+
+            let contacts: Vec<_> = hits.iter().map(|(contact, ..)| *contact).collect();
+            let paddle = hits.iter().find(|(.., is_paddle)| *is_paddle);
+            let Some(contact) = collisions::merge_contacts(&contacts, paddle.is_some()) else {
+                ball_center += motion;
+                break;
+            };
+
+            // an overlapping ball is pushed back out along the contact normal before it bounces,
+            // so it can never be left inside a brick or the paddle
+            ball_center += motion * earliest_time + contact.normal * contact.penetration;
+            remaining_secs *= 1.0 - earliest_time;
+            let collision = contact.collision;
+            commands.trigger(BallCollided { point: contact.point, normal: contact.normal, collision });
+
+            // every brick touched takes its hit, but the ball bounces only once
+            for (_, collider_entity, _, is_brick, _) in &hits {
+                if !is_brick {
+                    continue;
+                }
+                // steel only bounces the ball and does not feed the combo
+                if brick_query.get(*collider_entity).is_ok_and(|(_, brick, ..)| brick.kind.is_destructible()) {
+                    combo.hit();
+                }
+                let multiplier = combo.multiplier();
+                bricks::break_bricks(&mut commands, &mut score, &mut brick_query, &collider_grid, *collider_entity, multiplier, &mut destroyed, &config);
+            }
+
+            if hits.iter().any(|(_, collider_entity, ..)| bottom_wall_query.get(*collider_entity).is_ok()) {
+                // the bottom wall loses the ball
+                lost_balls.push(ball_entity);
+                break;
+            } else if let Some((_, _, paddle_box, ..)) = paddle {
+                combo.reset();
+                if active_power_ups.is_active(PowerUpKind::StickyPaddle) {
+                    // caught: it rides the paddle until the next serve
+                    **ball_velocity = Vec2::ZERO;
+                    commands.entity(ball_entity).insert(OnPaddle);
                     break;
                 }
+                collisions::paddle_reflection(&mut ball_velocity, collision, ball_center, *paddle_box, &config);
+                ball_cooldown.0 = config.paddle_hit_cooldown_ticks;
+            } else {
+                collisions::ball_reflection(&mut ball_velocity, collision);
             }
+        }
+
+        ball_transform.translation = ball_center.extend(ball_transform.translation.z);
+    }
 
-            collisions::ball_reflection(&mut ball_velocity, collision, is_paddle);
+    if lost_balls.is_empty() {
+        return;
+    }
+
+    // balls waiting on the paddle still count as in play
+    if all_balls_query.iter().len() > lost_balls.len() {
+        for ball_entity in lost_balls {
+            commands.entity(ball_entity).despawn();
         }
+        return;
     }
 
-    if game_over {
-        gameover_ui::game_over(
-            commands,
-            score,
-            state,
-            all_query,
-            meshes,
-            materials,
-            asset_server,
-        );
+    // the last balls went out together: keep one for the next life
+    let (kept_ball, extra_balls) = lost_balls.split_first().unwrap();
+    for ball_entity in extra_balls {
+        commands.entity(*ball_entity).despawn();
     }
 
-}
\ No newline at end of file
+    combo.reset();
+    if lives.lose_one() {
+        next_state.set(GameState::GameOver);
+    } else if let Ok((ball_entity, mut ball_velocity, _, mut ball_cooldown)) = ball_query.get_mut(*kept_ball) {
+        // hide the ball on the paddle until the countdown ends, then it waits for a serve
+        **ball_velocity = Vec2::ZERO;
+        ball_cooldown.0 = 0;
+        commands.entity(ball_entity).insert((OnPaddle, RespawnCountdown::new(config.ball_respawn_delay), Visibility::Hidden));
+        info!("Ball lost! {} lives left", lives.get());
+    }
+}
+
+// Runs after everything that can break bricks, once their despawns have been applied.
+pub(crate) fn check_stage_clear(
+    remaining_bricks: Res<RemainingBricks>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // losing the last ball on the same tick wins over clearing the stage
+    if matches!(*next_state, NextState::Pending(_)) {
+        return;
+    }
+    if remaining_bricks.get() == 0 {
+        next_state.set(GameState::StageClear);
+    }
+}
+
+// steel bricks never break and do not hold the stage open
+fn count_added_brick(add: On<Add, Brick>, bricks: Query<&Brick>, mut remaining_bricks: ResMut<RemainingBricks>) {
+    if bricks.get(add.entity).is_ok_and(|brick| brick.kind.is_destructible()) {
+        remaining_bricks.add_one();
+    }
+}
+
+fn count_removed_brick(remove: On<Remove, Brick>, bricks: Query<&Brick>, mut remaining_bricks: ResMut<RemainingBricks>) {
+    if bricks.get(remove.entity).is_ok_and(|brick| brick.kind.is_destructible()) {
+        remaining_bricks.remove_one();
+    }
+}
+
+// Bricks and walls never move once spawned, so they enter the grid once and leave it on despawn.
+fn add_to_collider_grid(
+    add: On<Add, Collider>,
+    mut collider_grid: ResMut<ColliderGrid>,
+    static_colliders: Query<&Transform, Without<Paddle>>,
+) {
+    if let Ok(transform) = static_colliders.get(add.entity) {
+        collider_grid.insert(add.entity, Aabb2d::new(transform.translation.truncate(), transform.scale.truncate() / 2.0));
+    }
+}
+
+fn remove_from_collider_grid(remove: On<Remove, Collider>, mut collider_grid: ResMut<ColliderGrid>) {
+    collider_grid.remove(remove.entity);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{input::InputPlugin, state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+    use super::*;
+
+    // one tick per update, with the floor below and a brick out of the way so the stage goes on
+    fn arena() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, InputPlugin, PhysicsPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1.0 / PHYSICS_TICK_RATE_HZ)));
+        app.world_mut().spawn((Wall, BottomWall, Transform::from_xyz(0.0, -300.0, 0.0).with_scale(Vec3::new(900.0, 10.0, 1.0))));
+        app.world_mut().spawn((
+            Brick::new(BrickKind::Normal),
+            Collider,
+            Sprite::default(),
+            Transform::from_xyz(-300.0, 250.0, 0.0).with_scale(Vec3::new(100.0, 30.0, 1.0)),
+        ));
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Playing);
+        app
+    }
+
+    fn spawn_ball(app: &mut App, position: Vec2, velocity: Vec2) -> Entity {
+        app.world_mut().spawn((
+            Ball,
+            Velocity::new(velocity),
+            PaddleCooldown(0),
+            Transform::from_translation(position.extend(BALL_Z)).with_scale(Vec3::splat(BALL_DIAMETER)),
+        )).id()
+    }
+
+    fn run(app: &mut App, updates: usize) {
+        for _ in 0..updates {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn losing_one_of_several_balls_costs_no_life() {
+        let mut app = arena();
+        let lives = app.world().resource::<Lives>().get();
+        let lost = spawn_ball(&mut app, Vec2::new(0.0, -250.0), Vec2::new(0.0, -400.0));
+        let kept = spawn_ball(&mut app, Vec2::ZERO, Vec2::new(0.0, 400.0));
+
+        run(&mut app, 30);
+
+        assert!(app.world().get_entity(lost).is_err());
+        assert!(!app.world().entity(kept).contains::<OnPaddle>());
+        assert_eq!(app.world().resource::<Lives>().get(), lives);
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Playing);
+    }
+
+    #[test]
+    fn losing_the_last_ball_costs_a_life_and_keeps_it_for_the_next() {
+        let mut app = arena();
+        let lives = app.world().resource::<Lives>().get();
+        let ball = spawn_ball(&mut app, Vec2::new(0.0, -250.0), Vec2::new(0.0, -400.0));
+
+        run(&mut app, 30);
+
+        let ball = app.world().entity(ball);
+        assert!(ball.contains::<OnPaddle>() && ball.contains::<RespawnCountdown>());
+        assert_eq!(**ball.get::<Velocity>().unwrap(), Vec2::ZERO);
+        assert_eq!(app.world().resource::<Lives>().get(), lives - 1);
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Playing);
+    }
+
+    #[test]
+    fn losing_the_last_ball_on_the_last_life_ends_the_game() {
+        let mut app = arena();
+        app.insert_resource(Lives::new(1));
+        spawn_ball(&mut app, Vec2::new(0.0, -250.0), Vec2::new(0.0, -400.0));
+
+        run(&mut app, 30);
+
+        assert_eq!(app.world().resource::<Lives>().get(), 0);
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::GameOver);
+    }
+}
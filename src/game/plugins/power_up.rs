@@ -0,0 +1,199 @@
+use bevy::{prelude::*, math::bounding::{Aabb2d, IntersectsVolume}};
+use crate::game::{prelude::*, plugins::physics::{check_for_collisions, check_stage_clear}, systems::physics::bricks};
+
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActivePowerUps>()
+            .insert_resource(PowerUpRng::new(POWER_UP_SEED))
+            .add_observer(drop_power_up)
+            .add_systems(OnExit(InGame), clear_power_ups)
+            .add_systems(FixedUpdate, (
+                collect_power_ups,
+                tick_power_ups,
+                fire_lasers,
+                hit_bricks_with_lasers,
+                apply_power_up_effects,
+            ).chain()
+                .after(check_for_collisions)
+                .before(check_stage_clear)
+                .run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn drop_power_up(
+    destroyed: On<BrickDestroyed>,
+    mut commands: Commands,
+    mut rng: ResMut<PowerUpRng>,
+) {
+    if !rng.chance(POWER_UP_DROP_CHANCE) {
+        return;
+    }
+    let kind = rng.pick(&PowerUpKind::ALL);
+    let translation = destroyed.position.extend(0.5);
+
+    commands.spawn((
+        Sprite::from_color(kind.color(), POWER_UP_SIZE),
+        Transform::from_translation(translation),
+        Interpolated::new(translation),
+        Velocity::new(Vec2::new(0.0, -POWER_UP_FALL_SPEED)),
+        PowerUpCapsule(kind),
+        DespawnOnExit(InGame),
+        children![(
+            Text2d::new(kind.label()),
+            TextFont {
+                font_size: POWER_UP_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::BLACK),
+            Transform::from_xyz(0.0, 0.0, 0.1),
+        )],
+    ));
+}
+
+fn collect_power_ups(
+    mut commands: Commands,
+    capsule_query: Query<(Entity, &Transform, &PowerUpCapsule)>,
+    paddle_transform: Single<&Transform, With<Paddle>>,
+    ball_query: Query<(&Transform, &Velocity, &Mesh2d, &MeshMaterial2d<ColorMaterial>), (With<Ball>, Without<OnPaddle>)>,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+    mut lives: ResMut<Lives>,
+    config: Res<GameConfig>,
+) {
+    let paddle_box = Aabb2d::new(
+        paddle_transform.translation.truncate(),
+        paddle_transform.scale.truncate() / 2.0,
+    );
+
+    for (entity, transform, capsule) in &capsule_query {
+        let capsule_box = Aabb2d::new(transform.translation.truncate(), POWER_UP_SIZE / 2.0);
+        if capsule_box.intersects(&paddle_box) {
+            match capsule.0 {
+                PowerUpKind::ExtraLife => lives.gain_one(),
+                PowerUpKind::MultiBall => split_balls(&mut commands, &ball_query),
+                kind => {
+                    if let Some(duration) = kind.duration() {
+                        active_power_ups.activate(kind, duration);
+                    }
+                }
+            }
+            commands.entity(entity).despawn();
+            info!("Power-up caught: {:?}", capsule.0);
+        } else if transform.translation.y < config.bottom_wall {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// Balls waiting on the paddle are not split.
+fn split_balls(
+    commands: &mut Commands,
+    ball_query: &Query<(&Transform, &Velocity, &Mesh2d, &MeshMaterial2d<ColorMaterial>), (With<Ball>, Without<OnPaddle>)>,
+) {
+    for (transform, velocity, mesh, material) in ball_query {
+        for angle in [-MULTI_BALL_SPREAD_ANGLE, MULTI_BALL_SPREAD_ANGLE] {
+            commands.spawn((
+                mesh.clone(),
+                material.clone(),
+                *transform,
+                Interpolated::new(transform.translation),
+                Ball,
+                Velocity::new(Vec2::from_angle(angle).rotate(**velocity)),
+                PaddleCooldown(0),
+                DespawnOnExit(InGame),
+            ));
+        }
+    }
+}
+
+fn tick_power_ups(time: Res<Time>, mut active_power_ups: ResMut<ActivePowerUps>) {
+    for kind in active_power_ups.tick(time.delta()) {
+        info!("Power-up expired: {kind:?}");
+    }
+}
+
+// one bolt from each end of the paddle while the laser is active
+fn fire_lasers(
+    mut commands: Commands,
+    time: Res<Time>,
+    active_power_ups: Res<ActivePowerUps>,
+    paddle_transform: Single<&Transform, With<Paddle>>,
+    mut cooldown: Local<f32>,
+) {
+    if !active_power_ups.is_active(PowerUpKind::Laser) {
+        *cooldown = 0.0;
+        return;
+    }
+    *cooldown -= time.delta_secs();
+    if *cooldown > 0.0 {
+        return;
+    }
+    *cooldown = LASER_FIRE_INTERVAL;
+
+    let paddle = paddle_transform.translation;
+    for side in [-1.0, 1.0] {
+        let translation = Vec3::new(
+            paddle.x + side * (paddle_transform.scale.x - LASER_SIZE.x) / 2.0,
+            paddle.y + (paddle_transform.scale.y + LASER_SIZE.y) / 2.0,
+            0.5,
+        );
+        commands.spawn((
+            Sprite::from_color(LASER_COLOR, LASER_SIZE),
+            Transform::from_translation(translation),
+            Interpolated::new(translation),
+            Velocity::new(Vec2::new(0.0, LASER_SPEED)),
+            LaserBolt,
+            DespawnOnExit(InGame),
+        ));
+    }
+}
+
+fn hit_bricks_with_lasers(
+    mut commands: Commands,
+    mut score: ResMut<Score>,
+    combo: Res<Combo>,
+    bolt_query: Query<(Entity, &Transform), With<LaserBolt>>,
+    mut brick_query: Query<(Entity, &mut Brick, &mut Sprite, &Transform), Without<Ball>>,
+    collider_grid: Res<ColliderGrid>,
+    config: Res<GameConfig>,
+) {
+    let mut destroyed = Vec::new();
+
+    for (bolt, bolt_transform) in &bolt_query {
+        let bolt_box = Aabb2d::new(bolt_transform.translation.truncate(), LASER_SIZE / 2.0);
+        let hit = brick_query.iter_many(collider_grid.query(bolt_box)).find_map(|(entity, _, _, transform)| {
+            let brick_box = Aabb2d::new(transform.translation.truncate(), transform.scale.truncate() / 2.0);
+            (!destroyed.contains(&entity) && bolt_box.intersects(&brick_box)).then_some(entity)
+        });
+
+        if let Some(brick) = hit {
+            // lasers score at the running multiplier without adding to the combo
+            bricks::break_bricks(&mut commands, &mut score, &mut brick_query, &collider_grid, brick, combo.multiplier(), &mut destroyed, &config);
+            commands.entity(bolt).despawn();
+        } else if bolt_transform.translation.y > config.top_wall {
+            commands.entity(bolt).despawn();
+        }
+    }
+}
+
+// Sets the paddle and balls to match the running effects, so an expired effect is undone
+// on the tick it runs out.
+fn apply_power_up_effects(
+    active_power_ups: Res<ActivePowerUps>,
+    mut paddle_transform: Single<&mut Transform, With<Paddle>>,
+    mut ball_query: Query<&mut Velocity, With<Ball>>,
+    config: Res<GameConfig>,
+) {
+    paddle_transform.scale.x = active_power_ups.paddle_width(&config);
+
+    let speed = active_power_ups.ball_speed(&config);
+    for mut velocity in &mut ball_query {
+        **velocity = velocity.normalize_or_zero() * speed;
+    }
+}
+
+// Leaving the game ends every effect; the paddle and balls they changed are despawned with it.
+fn clear_power_ups(mut active_power_ups: ResMut<ActivePowerUps>) {
+    active_power_ups.clear();
+}
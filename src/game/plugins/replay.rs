@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_real_timer};
+use crate::game::{
+    prelude::*,
+    plugins::{level::level_ready, start::reset_game},
+    systems::physics::paddle,
+};
+
+/// Records the paddle input of every physics tick of each stage to `ReplayFile`, and plays
+/// a recording back from the menu or one handed to `GamePlugin::with_replay`. The recording
+/// is also saved every few seconds and when the app exits, so quitting mid-stage keeps it.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayFile>();
+
+        app.add_systems(OnEnter(InGame), begin_recording)
+            .add_systems(OnExit(InGame), save_recording)
+            .add_systems(FixedUpdate, (
+                record_tick.run_if(resource_exists::<ReplayRecording>),
+                play_back_tick.run_if(resource_exists::<ReplayPlayback>),
+            ).before(paddle::move_paddle).run_if(in_state(GameState::Playing)))
+            .add_systems(Update, flush_recording.run_if(
+                resource_exists::<ReplayRecording>.and(on_real_timer(Duration::from_secs_f32(REPLAY_FLUSH_INTERVAL))),
+            ))
+            .add_systems(Last, save_recording_on_exit.run_if(resource_exists::<ReplayRecording>))
+            .add_systems(Update, (input_watch_replay, start_pending_replay)
+                .chain()
+                .run_if(in_state(GameState::Menu).and(level_ready)))
+            .add_systems(OnEnter(GameState::GameOver), finish_playback)
+            .add_systems(OnEnter(GameState::StageClear), finish_playback)
+            .add_systems(OnEnter(GameState::Menu), end_playback.before(reset_game));
+    }
+}
+
+// Also run by the pause menu's Restart, which rebuilds the stage without leaving `InGame`.
+pub(crate) fn begin_recording(
+    mut commands: Commands,
+    playback: Option<Res<ReplayPlayback>>,
+    current_level: Res<CurrentLevel>,
+    power_up_rng: Res<PowerUpRng>,
+    lives: Res<Lives>,
+    score: Res<Score>,
+    config: Res<GameConfig>,
+) {
+    if playback.is_some() {
+        return;
+    }
+    commands.insert_resource(ReplayRecording(Replay::new(
+        current_level.index(),
+        power_up_rng.seed(),
+        lives.get(),
+        score.get(),
+        config.clone(),
+    )));
+}
+
+fn save_recording(
+    mut commands: Commands,
+    recording: Option<Res<ReplayRecording>>,
+    replay_file: Res<ReplayFile>,
+) {
+    let Some(recording) = recording else { return; };
+    commands.remove_resource::<ReplayRecording>();
+    if write_recording(&recording, &replay_file) {
+        info!("Recorded {} ticks to {}", recording.tick_count(), replay_file.display());
+    }
+}
+
+// the stage so far, in case the game is killed before it ends
+fn flush_recording(
+    recording: Res<ReplayRecording>,
+    replay_file: Res<ReplayFile>,
+) {
+    write_recording(&recording, &replay_file);
+}
+
+// `OnExit(InGame)` never runs when the window is closed mid-stage
+fn save_recording_on_exit(
+    mut exits: MessageReader<AppExit>,
+    recording: Res<ReplayRecording>,
+    replay_file: Res<ReplayFile>,
+) {
+    if exits.read().next().is_some() && write_recording(&recording, &replay_file) {
+        info!("Recorded {} ticks to {}", recording.tick_count(), replay_file.display());
+    }
+}
+
+fn write_recording(recording: &ReplayRecording, replay_file: &ReplayFile) -> bool {
+    match recording.save(replay_file) {
+        Ok(()) => true,
+        Err(err) => {
+            warn!("Could not save replay to {}: {err}", replay_file.display());
+            false
+        }
+    }
+}
+
+// Runs before `serve_ball` consumes the serve.
+fn record_tick(
+    paddle_input: Res<PaddleInput>,
+    mut recording: ResMut<ReplayRecording>,
+) {
+    recording.push(TickInput::read(&paddle_input));
+}
+
+// Overwrites whatever the player did this frame with the recorded tick.
+fn play_back_tick(
+    mut playback: ResMut<ReplayPlayback>,
+    mut paddle_input: ResMut<PaddleInput>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    match playback.next_tick() {
+        Some(input) => input.apply(&mut paddle_input),
+        None => {
+            // the recording was quit before the stage ended
+            *paddle_input = PaddleInput::default();
+            next_state.set(GameState::Menu);
+        }
+    }
+}
+
+fn input_watch_replay(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    replay_file: Res<ReplayFile>,
+) {
+    if actions.just_pressed(Action::WatchReplay) {
+        commands.insert_resource(PendingReplay(replay_file.0.clone()));
+    }
+}
+
+// Puts the game back where the recorded stage started, then enters it.
+fn start_pending_replay(
+    mut commands: Commands,
+    pending: Option<Res<PendingReplay>>,
+    campaigns: Res<Assets<Campaign>>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut lives: ResMut<Lives>,
+    mut score: ResMut<Score>,
+    mut power_up_rng: ResMut<PowerUpRng>,
+    mut config: ResMut<GameConfig>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(pending) = pending else { return; };
+    commands.remove_resource::<PendingReplay>();
+
+    let replay = match Replay::load(&pending) {
+        Ok(replay) => replay,
+        Err(err) => {
+            warn!("Could not play back {}: {err}", pending.display());
+            return;
+        }
+    };
+    let n_levels = campaigns.get(current_level.campaign()).map_or(0, |campaign| campaign.levels.len());
+    if replay.level >= n_levels {
+        warn!("Could not play back {}: the campaign has no stage {}", pending.display(), replay.level + 1);
+        return;
+    }
+
+    current_level.jump_to(replay.level);
+    **lives = replay.lives;
+    score.set(replay.score);
+    *power_up_rng = PowerUpRng::new(replay.seed);
+    let player_config = std::mem::replace(&mut *config, replay.config.clone());
+    // the physics step was set from the player's settings when the app was built
+    fixed_time.set_timestep_hz(replay.config.physics_tick_rate);
+    info!("Playing back {} ticks of stage {}", replay.tick_count(), replay.level + 1);
+    commands.insert_resource(ReplayPlayback::new(replay, player_config));
+    next_state.set(GameState::Playing);
+}
+
+// a played-back stage goes straight back to the menu once it ends
+fn finish_playback(
+    playback: Option<Res<ReplayPlayback>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if playback.is_some() {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn end_playback(
+    mut commands: Commands,
+    playback: Option<Res<ReplayPlayback>>,
+    mut config: ResMut<GameConfig>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+) {
+    let Some(playback) = playback else { return; };
+    *config = playback.player_config().clone();
+    fixed_time.set_timestep_hz(config.physics_tick_rate);
+    commands.remove_resource::<ReplayPlayback>();
+    info!("Replay finished");
+}
@@ -5,8 +5,9 @@ pub struct ResourcePlugin;
 
 impl Plugin for ResourcePlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Score::new(0))
-            .insert_resource(GameState::Menu);
+        app.init_resource::<Score>()
+            .init_resource::<Lives>()
+            .init_state::<GameState>()
+            .add_computed_state::<InGame>();
     }
 }
-
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use crate::game::{prelude::*, systems::ui::settings_ui};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsSelection>()
+            .add_systems(OnEnter(GameState::Settings), setup_settings)
+            .add_systems(Update, (
+                input_settings,
+                settings_ui::update_settings_ui.run_if(
+                    resource_changed::<SettingsSelection>
+                        .or(resource_changed::<InputBindings>)
+                        .or(resource_changed::<GameConfig>)
+                ),
+            ).chain().run_if(in_state(GameState::Settings)));
+    }
+}
+
+fn setup_settings(
+    mut commands: Commands,
+    mut selection: ResMut<SettingsSelection>,
+    config: Res<GameConfig>,
+) {
+    *selection = SettingsSelection::default();
+    settings_ui::spawn_settings_ui(&mut commands, &config);
+}
+
+fn input_settings(
+    actions: Res<ActionState>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut selection: ResMut<SettingsSelection>,
+    mut bindings: ResMut<InputBindings>,
+    bindings_file: Res<InputBindingsFile>,
+    mut config: ResMut<GameConfig>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // while listening every input is a candidate, including the ones bound to menu actions
+    if selection.listening {
+        let Some(binding) = just_pressed_binding(&keys, &mouse_buttons, &gamepads) else { return; };
+        let action = Action::ALL[selection.row];
+        bindings.rebind(action, binding);
+        if let Err(err) = bindings.save(&bindings_file) {
+            warn!("Could not save controls to {}: {err}", bindings_file.display());
+        }
+        selection.listening = false;
+        info!("Bound {} to {}", binding.label(), action.label());
+        return;
+    }
+
+    if actions.just_pressed(Action::Back) {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    let n_rows = SettingsRow::COUNT;
+    if actions.just_pressed(Action::MenuUp) {
+        selection.row = (selection.row + n_rows - 1) % n_rows;
+    }
+    if actions.just_pressed(Action::MenuDown) {
+        selection.row = (selection.row + 1) % n_rows;
+    }
+    if actions.just_pressed(Action::Confirm) {
+        match SettingsRow::at(selection.row) {
+            // the press that starts listening was this frame, so it cannot be captured itself
+            SettingsRow::Binding(_) => selection.listening = true,
+            // for this session; `paddle_control` in the settings file sets the starting mode
+            SettingsRow::PaddleControl => {
+                config.paddle_control = config.paddle_control.toggled();
+                info!("Paddle control set to {}", config.paddle_control.label());
+            }
+        }
+    }
+}
+
+// the first key, mouse button or gamepad button pressed this frame
+fn just_pressed_binding(
+    keys: &ButtonInput<KeyCode>,
+    mouse_buttons: &ButtonInput<MouseButton>,
+    gamepads: &Query<&Gamepad>,
+) -> Option<Binding> {
+    keys.get_just_pressed().next().map(|key| Binding::Key(*key))
+        .or_else(|| mouse_buttons.get_just_pressed().next().map(|button| Binding::Mouse(*button)))
+        .or_else(|| gamepads.iter().find_map(|gamepad| gamepad.get_just_pressed().next()).map(|button| Binding::Gamepad(*button)))
+}
@@ -5,30 +5,36 @@ pub struct StartPlugin;
 
 impl Plugin for StartPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_start_ui);
+        app.add_systems(OnEnter(GameState::Menu), (reset_game, setup_start_ui));
     }
 }
 
+// every return to the menu starts a fresh run from the first stage
+pub(crate) fn reset_game(
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut combo: ResMut<Combo>,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+    mut power_up_rng: ResMut<PowerUpRng>,
+    config: Res<GameConfig>,
+) {
+    score.set_zero();
+    **lives = config.starting_lives;
+    current_level.restart();
+    combo.reset();
+    active_power_ups.clear();
+    *power_up_rng = PowerUpRng::new(POWER_UP_SEED);
+}
+
 fn setup_start_ui(
     mut commands: Commands,
-    meshes: ResMut<Assets<Mesh>>,
-    materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
+    high_scores: Res<HighScores>,
+    bindings: Res<InputBindings>,
+    config: Res<GameConfig>,
 ) {
-    // Camera
-    // commands.spawn(Camera2d);
-
     // Simple start screen as a large sprite; text rendering setup may vary across projects,
     // so we keep the start screen visual minimal here.
     // semi-transparent full-screen overlay for start menu
-    start_ui::spawn_start_ui(&mut commands);
-
-    // Preload collision sound resource so it's available when level spawns
-    let ball_collision_sound = asset_server.load("sounds/breakout_collision.ogg");
-    commands.insert_resource(CollisionSound::new(ball_collision_sound));
-
-    // Spawn the level in the background while showing the StartUi overlay.
-    // Systems that update movement/collisions check `GameState` and won't run
-    // until the player starts the game, so the scene will be static behind the overlay.
-    spawn_breakout_ui(commands, meshes, materials, asset_server);
-}
\ No newline at end of file
+    start_ui::spawn_start_ui(&mut commands, &high_scores, &bindings, &config);
+}
@@ -5,14 +5,26 @@ pub struct ScoreboardPlugin;
 
 impl Plugin for ScoreboardPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_scoreboard);
+        app.add_systems(Update, (update_scoreboard, update_countdown).run_if(in_state(InGame)));
     }
 }
 
 fn update_scoreboard(
     score: Res<Score>,
+    lives: Res<Lives>,
+    combo: Res<Combo>,
     score_root: Single<Entity, (With<ScoreboardUi>, With<Text>)>,
     mut writer: TextUiWriter,
 ) {
     *writer.text(*score_root, 1) = score.to_string();
+    *writer.text(*score_root, 3) = lives.to_string();
+    *writer.text(*score_root, 5) = format!("{} x{}", combo.get(), combo.multiplier());
+}
+
+fn update_countdown(
+    countdown_query: Query<&RespawnCountdown, With<Ball>>,
+    mut countdown_text: Single<&mut Text, With<CountdownUi>>,
+) {
+    let remaining = countdown_query.iter().map(|countdown| countdown.remaining_secs()).fold(0.0, f32::max);
+    countdown_text.0 = if remaining > 0.0 { (remaining.ceil() as u32).to_string() } else { String::new() };
 }
\ No newline at end of file
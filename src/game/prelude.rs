@@ -1,6 +1,14 @@
-pub(crate) use crate::game::core::components::*;
+pub use crate::game::core::components::*;
 pub(crate) use crate::game::core::constants::*;
-pub(crate) use crate::game::core::resources::*;
-pub(crate) use crate::game::core::events::*;
+pub use crate::game::core::resources::*;
+pub use crate::game::core::events::*;
+pub use crate::game::core::level::*;
+pub use crate::game::core::states::*;
+pub use crate::game::core::high_scores::*;
+pub use crate::game::core::config::*;
+pub use crate::game::core::persistence::*;
+pub use crate::game::core::actions::*;
+pub use crate::game::core::replay::*;
+pub use crate::game::core::broadphase::*;
 
 pub(crate) use crate::game::systems::ui::breakout_ui::spawn_breakout_ui;
\ No newline at end of file
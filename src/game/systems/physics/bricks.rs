@@ -0,0 +1,83 @@
+use bevy::{prelude::*, math::bounding::Aabb2d};
+use crate::game::prelude::*;
+
+// Applies one ball hit to a brick and returns every brick it destroys, explosions included.
+// Bricks in `already_destroyed` are waiting for their deferred despawn and are left alone.
+pub fn hit_brick(
+    entity: Entity,
+    brick_query: &mut Query<(Entity, &mut Brick, &mut Sprite, &Transform), Without<Ball>>,
+    collider_grid: &ColliderGrid,
+    already_destroyed: &[Entity],
+    config: &GameConfig,
+) -> Vec<(Entity, BrickKind)> {
+    let Ok((_, mut brick, mut sprite, _)) = brick_query.get_mut(entity) else {
+        return Vec::new();
+    };
+    if !brick.kind.is_destructible() {
+        return Vec::new();
+    }
+
+    brick.health = brick.health.saturating_sub(1);
+    if brick.health > 0 {
+        sprite.color = brick.color(config);
+        return Vec::new();
+    }
+
+    let mut destroyed = vec![(entity, brick.kind)];
+    let mut next = 0;
+    while next < destroyed.len() {
+        let (exploding, kind) = destroyed[next];
+        next += 1;
+        if kind != BrickKind::Explosive {
+            continue;
+        }
+
+        let Ok((_, _, _, exploding_transform)) = brick_query.get(exploding) else {
+            continue;
+        };
+        let center = exploding_transform.translation.truncate();
+        let reach = exploding_transform.scale.truncate() * EXPLOSION_REACH;
+
+        let mut caught = Vec::new();
+        let blast = Aabb2d::new(center, reach);
+        for (other, other_brick, _, other_transform) in brick_query.iter_many(collider_grid.query(blast)) {
+            let offset = (other_transform.translation.truncate() - center).abs();
+            if other_brick.kind.is_destructible()
+                && offset.x <= reach.x
+                && offset.y <= reach.y
+                && !already_destroyed.contains(&other)
+                && !destroyed.iter().any(|(entity, _)| *entity == other)
+            {
+                caught.push((other, other_brick.kind));
+            }
+        }
+        destroyed.extend(caught);
+    }
+
+    destroyed
+}
+
+// Hits a brick, then despawns, scores and announces every brick that breaks because of it.
+pub fn break_bricks(
+    commands: &mut Commands,
+    score: &mut Score,
+    brick_query: &mut Query<(Entity, &mut Brick, &mut Sprite, &Transform), Without<Ball>>,
+    collider_grid: &ColliderGrid,
+    entity: Entity,
+    multiplier: usize,
+    destroyed: &mut Vec<Entity>,
+    config: &GameConfig,
+) {
+    for (brick_entity, kind) in hit_brick(entity, brick_query, collider_grid, destroyed, config) {
+        if let Ok((.., transform)) = brick_query.get(brick_entity) {
+            commands.trigger(BrickDestroyed {
+                position: transform.translation.truncate(),
+                points: kind.score(),
+                multiplier,
+            });
+        }
+        commands.entity(brick_entity).despawn();
+        destroyed.push(brick_entity);
+        **score += kind.score() * multiplier;
+    }
+}
@@ -1,95 +1,210 @@
-use bevy::{prelude::*, math::bounding::{Aabb2d, BoundingCircle, IntersectsVolume}};
+use bevy::{prelude::*, math::bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume}};
 
-use crate::game::CORNER_THRESHOLD;
+use crate::game::prelude::GameConfig;
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+// shared with `BallCollided`, and kept reachable from here as part of the public collision API
+pub use crate::game::core::collision::{Collision, Corner};
 
-pub enum Corner {
-    UpperLeft,
-    UpperRight,
-    LowerLeft,
-    LowerRight,
-}
-pub enum Collision {
-    Left,
-    Right,
-    Top,
-    Bottom,
-    Corner(Vec2, Corner),
+// How the ball touches a collider.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Contact {
+    // unit normal pointing from the box towards the ball
+    pub normal: Vec2,
+    // how far the ball must move along `normal` to stop overlapping, 0.0 when just touching
+    pub penetration: f32,
+    // point on the box surface closest to the ball centre
+    pub point: Vec2,
+    pub collision: Collision,
 }
 
+// The contact between an overlapping ball and box, `None` when they do not touch.
+// A ball whose centre is outside the box is pushed straight away from the closest point,
+// so only a centre beyond both edges (the rounded corner region) counts as a corner hit.
+// A centre inside the box leaves through the nearest face.
 pub fn ball_collision(
     ball: BoundingCircle,
     bounding_box: Aabb2d,
-    paddle_hit: bool
-) -> Option<Collision>{
+    paddle_hit: bool,
+) -> Option<Contact> {
     if !ball.intersects(&bounding_box) {
         return None;
     }
 
     let closest = bounding_box.closest_point(ball.center);
     let offset = ball.center - closest;
+    let distance = offset.length();
+    if distance > f32::EPSILON {
+        let normal = offset / distance;
+        return Some(Contact {
+            normal,
+            penetration: ball.radius() - distance,
+            point: closest,
+            collision: Collision::from_normal(normal, paddle_hit),
+        });
+    }
 
-    let abs_x = offset.x.abs();
-    let abs_y = offset.y.abs();
-    if (abs_x - abs_y).abs() <= CORNER_THRESHOLD {
-        if offset.length_squared() <= 1e-6 {
-            return Some(Collision::Top)
-        } else {
-            if paddle_hit {
-                if offset.y <= 0.0 {
-                    return Some(Collision::Bottom);
-                }
-            }
-            // 4つの角のどれかを判定
-            let corner = if offset.x >= 0.0 && offset.y >= 0.0 {
-                Corner::UpperRight
-            } else if offset.x < 0.0 && offset.y >= 0.0 {
-                Corner::UpperLeft
-            } else if offset.x >= 0.0 && offset.y < 0.0 {
-                Corner::LowerRight
-            } else {
-                Corner::LowerLeft
-            };
-            return Some(Collision::Corner(offset.normalize(), corner));
+    // distance from the centre to each face, in the order of their normals
+    let faces = [
+        (bounding_box.max.y - ball.center.y, Vec2::Y),
+        (ball.center.y - bounding_box.min.y, Vec2::NEG_Y),
+        (ball.center.x - bounding_box.min.x, Vec2::NEG_X),
+        (bounding_box.max.x - ball.center.x, Vec2::X),
+    ];
+    let (depth, normal) = faces.into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .unwrap_or((0.0, Vec2::Y));
+    Some(Contact {
+        normal,
+        penetration: depth + ball.radius(),
+        point: ball.center + normal * depth,
+        collision: Collision::from_normal(normal, paddle_hit),
+    })
+}
+
+// Continuous version of `ball_collision`: moves the ball along `motion` and returns the
+// earliest time of impact as a fraction of `motion` (0.0..=1.0) together with the contact.
+// A ball that already overlaps the box only collides while it is moving into it.
+pub fn swept_ball_collision(
+    ball: BoundingCircle,
+    motion: Vec2,
+    bounding_box: Aabb2d,
+    paddle_hit: bool,
+) -> Option<(f32, Contact)> {
+    if ball.intersects(&bounding_box) {
+        let contact = ball_collision(ball, bounding_box, paddle_hit)?;
+        return (motion.dot(contact.normal) < 0.0).then_some((0.0, contact));
+    }
+
+    let (time, normal) = sweep_circle_aabb(ball.center, ball.radius(), motion, bounding_box)?;
+    let center = ball.center + motion * time;
+    Some((time, Contact {
+        normal,
+        penetration: 0.0,
+        point: center - normal * ball.radius(),
+        collision: Collision::from_normal(normal, paddle_hit),
+    }))
+}
+
+// Resolves contacts made at the same moment as one: the ball bounces off the average of their
+// distinct normals and is pushed out far enough to clear all of them. Touching two bricks across
+// a seam then reflects the ball once, instead of twice or sideways off one brick's corner.
+pub fn merge_contacts(contacts: &[Contact], paddle_hit: bool) -> Option<Contact> {
+    let first = contacts.first()?;
+    // a brick corner met together with a face is the inside edge of a seam, not something to bounce off
+    let touches_face = contacts.iter().any(|contact| !matches!(contact.collision, Collision::Corner(..)));
+    let mut normals: Vec<Vec2> = Vec::new();
+    for contact in contacts {
+        if touches_face && matches!(contact.collision, Collision::Corner(..)) {
+            continue;
+        }
+        if !normals.iter().any(|normal| normal.abs_diff_eq(contact.normal, 1e-4)) {
+            normals.push(contact.normal);
         }
     }
+    // opposite normals cancel out (the ball is squeezed between two colliders)
+    let mut normal = normals.iter().sum::<Vec2>().try_normalize().unwrap_or(first.normal);
+    // symmetric corner normals leave rounding noise behind, which would read as a corner hit
+    for axis in 0..2 {
+        if normal[axis].abs() < 1e-4 {
+            normal[axis] = 0.0;
+            normal[1 - axis] = normal[1 - axis].signum();
+        }
+    }
+
+    let penetration = contacts.iter()
+        .filter_map(|contact| {
+            let along = contact.normal.dot(normal);
+            (along > f32::EPSILON).then(|| contact.penetration / along)
+        })
+        .fold(0.0, f32::max);
+    let point = contacts.iter().map(|contact| contact.point).sum::<Vec2>() / contacts.len() as f32;
+    Some(Contact { normal, penetration, point, collision: Collision::from_normal(normal, paddle_hit) })
+}
 
-    let side = if offset.x.abs() > offset.y.abs() {
-        if offset.x < 0.0 {
-            Collision::Left
-        } else {
-            Collision::Right
+// Sweeps a circle against a box by casting its centre against the box grown by the radius,
+// whose corners are rounded: quarter circles of `radius` around the original corners.
+fn sweep_circle_aabb(
+    center: Vec2,
+    radius: f32,
+    motion: Vec2,
+    bounding_box: Aabb2d,
+) -> Option<(f32, Vec2)> {
+    let box_center = bounding_box.center();
+    let half_size = bounding_box.half_size();
+    let grown_min = bounding_box.min - Vec2::splat(radius);
+    let grown_max = bounding_box.max + Vec2::splat(radius);
+
+    // slab test against the grown box
+    let mut t_enter = 0.0_f32;
+    let mut t_exit = 1.0_f32;
+    let mut normal = Vec2::ZERO;
+    for axis in 0..2 {
+        if motion[axis].abs() <= f32::EPSILON {
+            if center[axis] < grown_min[axis] || center[axis] > grown_max[axis] {
+                return None;
+            }
+            continue;
+        }
+
+        let mut t_near = (grown_min[axis] - center[axis]) / motion[axis];
+        let mut t_far = (grown_max[axis] - center[axis]) / motion[axis];
+        let mut axis_normal = -1.0;
+        if t_near > t_far {
+            std::mem::swap(&mut t_near, &mut t_far);
+            axis_normal = 1.0;
+        }
+        if t_near > t_enter {
+            t_enter = t_near;
+            normal = Vec2::ZERO;
+            normal[axis] = axis_normal;
         }
-    } else if offset.y > 0.0 {
-        Collision::Top
-    } else {
-        Collision::Bottom
-    };
+        t_exit = t_exit.min(t_far);
+        if t_enter > t_exit {
+            return None;
+        }
+    }
+
+    let entry = center + motion * t_enter;
+    let offset = entry - box_center;
+    let in_corner_region = offset.x.abs() > half_size.x && offset.y.abs() > half_size.y;
+    if !in_corner_region {
+        // starting inside the grown box without touching it only happens near a corner
+        return (normal != Vec2::ZERO).then_some((t_enter, normal));
+    }
 
-    Some(side)
+    let corner = box_center + half_size * offset.signum();
+    let time = sweep_point_circle(center, motion, corner, radius)?;
+    let contact = center + motion * time;
+    Some((time, (contact - corner).normalize()))
+}
+
+fn sweep_point_circle(
+    origin: Vec2,
+    motion: Vec2,
+    circle_center: Vec2,
+    radius: f32,
+) -> Option<f32> {
+    let to_origin = origin - circle_center;
+    let a = motion.length_squared();
+    let b = to_origin.dot(motion);
+    let c = to_origin.length_squared() - radius * radius;
+    // already inside, moving away, or not moving at all
+    if c <= 0.0 || b >= 0.0 || a <= f32::EPSILON {
+        return None;
+    }
+
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let time = (-b - discriminant.sqrt()) / a;
+    (0.0..=1.0).contains(&time).then_some(time)
 }
 
 pub fn ball_reflection(
     ball_velocity: &mut Vec2,
     collision: Collision,
-    paddle_hit: bool,
 ) {
-    if paddle_hit {
-        // Optionally, add logic for modifying ball velocity based on where it hit the paddle
-        // For example, you could change the angle of reflection based on the hit position
-        match collision {
-            Collision::Bottom => return,
-            Collision::Corner(_normal, corner) => {
-                match corner {
-                    Corner::LowerRight | Corner::LowerLeft => return,
-                    _ => {}
-                }
-            }
-            _ => {}
-        }
-    }
-
     let mut reflect_x = false;
     let mut reflect_y = false;
 
@@ -109,13 +224,229 @@ pub fn ball_reflection(
     }
 }
 
+pub fn paddle_reflection(
+    ball_velocity: &mut Vec2,
+    collision: Collision,
+    ball_center: Vec2,
+    paddle: Aabb2d,
+    config: &GameConfig,
+) {
+    match collision {
+        // the ball is already past the paddle, let it go
+        Collision::Bottom | Collision::Corner(_, Corner::LowerLeft | Corner::LowerRight) => {}
+        Collision::Left | Collision::Right => ball_reflection(ball_velocity, collision),
+        Collision::Top | Collision::Corner(_, Corner::UpperLeft | Corner::UpperRight) => {
+            if ball_velocity.y > 0.0 { return; }
+            let offset = ball_center.x - paddle.center().x;
+            *ball_velocity = paddle_bounce_direction(offset, paddle.half_size().x, config) * ball_velocity.length();
+        }
+    }
+}
+
+// Classic Breakout aiming: a hit at the centre goes up at `paddle_bounce_max_angle`,
+// moving towards the edges lowers the angle to `paddle_bounce_min_angle` (both from horizontal).
+pub fn paddle_bounce_direction(offset: f32, half_width: f32, config: &GameConfig) -> Vec2 {
+    let t = (offset / half_width).clamp(-1.0, 1.0);
+    let (min_angle, max_angle) = (config.paddle_bounce_min_angle.to_radians(), config.paddle_bounce_max_angle.to_radians());
+    let angle = max_angle + (min_angle - max_angle) * t.abs();
+    Vec2::new(angle.cos() * t.signum(), angle.sin())
+}
+
 fn reflection_corner(
     ball_velocity: &mut Vec2,
     normal: Vec2,
 ) {
-    let normal = normal;
     let velocity_along_normal = ball_velocity.dot(normal);
     let reflection = *ball_velocity - 2.0 * velocity_along_normal * normal;
     *ball_velocity = reflection;
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const RADIUS: f32 = 15.0;
+
+    fn ball_at(x: f32, y: f32) -> BoundingCircle {
+        BoundingCircle::new(Vec2::new(x, y), RADIUS)
+    }
+
+    #[test]
+    fn fast_ball_does_not_tunnel_through_thin_wall() {
+        // a 10px wall that the ball jumps over completely in one step
+        let wall = Aabb2d::new(Vec2::new(50.0, 0.0), Vec2::new(5.0, 100.0));
+        let motion = Vec2::new(200.0, 0.0);
+        assert!(!ball_at(200.0, 0.0).intersects(&wall));
+
+        let (time, contact) = swept_ball_collision(ball_at(0.0, 0.0), motion, wall, false).unwrap();
+        assert!((time - 30.0 / 200.0).abs() < 1e-5);
+        assert_eq!(contact.collision, Collision::Left);
+    }
+
+    #[test]
+    fn fast_ball_does_not_tunnel_through_brick() {
+        // moving down through a 30px brick from above
+        let brick = Aabb2d::new(Vec2::new(0.0, 0.0), Vec2::new(50.0, 15.0));
+        let motion = Vec2::new(0.0, -300.0);
+
+        let (time, contact) = swept_ball_collision(ball_at(10.0, 100.0), motion, brick, false).unwrap();
+        assert!((time - 70.0 / 300.0).abs() < 1e-5);
+        assert_eq!(contact.collision, Collision::Top);
+    }
+
+    #[test]
+    fn earliest_of_two_bricks_is_hit_first() {
+        let near = Aabb2d::new(Vec2::new(0.0, 100.0), Vec2::new(50.0, 15.0));
+        let far = Aabb2d::new(Vec2::new(0.0, 200.0), Vec2::new(50.0, 15.0));
+        let motion = Vec2::new(0.0, 400.0);
+
+        let (near_time, _) = swept_ball_collision(ball_at(0.0, 0.0), motion, near, false).unwrap();
+        let (far_time, _) = swept_ball_collision(ball_at(0.0, 0.0), motion, far, false).unwrap();
+        assert!(near_time < far_time);
+    }
+
+    #[test]
+    fn diagonal_ball_hits_rounded_corner() {
+        let brick = Aabb2d::new(Vec2::ZERO, Vec2::new(50.0, 15.0));
+        let motion = Vec2::new(-200.0, -200.0);
+
+        let (time, contact) = swept_ball_collision(ball_at(150.0, 115.0), motion, brick, false).unwrap();
+        let center = Vec2::new(150.0, 115.0) + motion * time;
+        assert!((center.distance(Vec2::new(50.0, 15.0)) - RADIUS).abs() < 1e-3);
+        assert!(contact.point.distance(Vec2::new(50.0, 15.0)) < 1e-3);
+        assert!(matches!(contact.collision, Collision::Corner(_, Corner::UpperRight)));
+    }
+
+    #[test]
+    fn ball_passing_beside_corner_misses() {
+        // inside the grown box's corner square but outside the rounded corner
+        let brick = Aabb2d::new(Vec2::ZERO, Vec2::new(50.0, 15.0));
+        let motion = Vec2::new(60.0, -60.0);
+
+        assert!(swept_ball_collision(ball_at(40.0, 50.0), motion, brick, false).is_none());
+    }
+
+    #[test]
+    fn ball_moving_away_or_falling_short_misses() {
+        let wall = Aabb2d::new(Vec2::new(50.0, 0.0), Vec2::new(5.0, 100.0));
+
+        assert!(swept_ball_collision(ball_at(0.0, 0.0), Vec2::new(-200.0, 0.0), wall, false).is_none());
+        assert!(swept_ball_collision(ball_at(0.0, 0.0), Vec2::new(20.0, 0.0), wall, false).is_none());
+    }
+
+    #[test]
+    fn overlapping_ball_only_collides_when_moving_in() {
+        let wall = Aabb2d::new(Vec2::new(50.0, 0.0), Vec2::new(5.0, 100.0));
+
+        let (time, contact) = swept_ball_collision(ball_at(35.0, 0.0), Vec2::new(10.0, 0.0), wall, false).unwrap();
+        assert_eq!(time, 0.0);
+        assert_eq!(contact.collision, Collision::Left);
+        assert!(swept_ball_collision(ball_at(35.0, 0.0), Vec2::new(-10.0, 0.0), wall, false).is_none());
+    }
+
+    #[test]
+    fn ball_near_corner_hits_the_face_it_overlaps() {
+        // centre above the top face, 2px in from the corner: a face hit, not a corner hit
+        let brick = Aabb2d::new(Vec2::ZERO, Vec2::new(50.0, 15.0));
+
+        let contact = ball_collision(ball_at(48.0, 25.0), brick, false).unwrap();
+        assert_eq!(contact.collision, Collision::Top);
+        assert_eq!(contact.normal, Vec2::Y);
+        assert!((contact.penetration - 5.0).abs() < 1e-5);
+        assert_eq!(contact.point, Vec2::new(48.0, 15.0));
+    }
+
+    #[test]
+    fn deeply_overlapping_ball_is_pushed_out_of_the_nearest_face() {
+        // centre inside the brick, 5px below its top face
+        let brick = Aabb2d::new(Vec2::ZERO, Vec2::new(50.0, 15.0));
+
+        let contact = ball_collision(ball_at(20.0, 10.0), brick, false).unwrap();
+        assert_eq!(contact.collision, Collision::Top);
+        assert!((contact.penetration - (5.0 + RADIUS)).abs() < 1e-5);
+        assert_eq!(contact.point, Vec2::new(20.0, 15.0));
+
+        let pushed = ball_at(20.0, 10.0).center + contact.normal * contact.penetration;
+        assert!(ball_collision(ball_at(pushed.x, pushed.y), brick, false).is_none_or(|c| c.penetration.abs() < 1e-4));
+    }
+
+    // two bricks side by side with `gap` between them, tops at y = 15
+    fn bricks_with_gap(gap: f32) -> [Aabb2d; 2] {
+        [
+            Aabb2d::new(Vec2::new(-50.0 - gap / 2.0, 0.0), Vec2::new(50.0, 15.0)),
+            Aabb2d::new(Vec2::new(50.0 + gap / 2.0, 0.0), Vec2::new(50.0, 15.0)),
+        ]
+    }
+
+    // sweeps against every brick and keeps the contacts made first, as `check_for_collisions` does
+    fn earliest_contacts(ball: BoundingCircle, motion: Vec2, bricks: &[Aabb2d]) -> Vec<Contact> {
+        let hits: Vec<(f32, Contact)> = bricks.iter()
+            .filter_map(|brick| swept_ball_collision(ball, motion, *brick, false))
+            .collect();
+        let earliest = hits.iter().map(|(time, _)| *time).fold(f32::INFINITY, f32::min);
+        hits.into_iter().filter(|(time, _)| *time - earliest < 1e-4).map(|(_, contact)| contact).collect()
+    }
+
+    #[test]
+    fn ball_falling_onto_a_seam_touches_both_bricks_and_bounces_straight_back() {
+        // the 5px gap is narrower than the ball, which lands on both inner corners at once
+        let bricks = bricks_with_gap(5.0);
+        let motion = Vec2::new(0.0, -100.0);
+
+        let contacts = earliest_contacts(ball_at(0.0, 60.0), motion, &bricks);
+        assert_eq!(contacts.len(), 2);
+        assert!(contacts.iter().all(|contact| matches!(contact.collision, Collision::Corner(..))));
+
+        let merged = merge_contacts(&contacts, false).unwrap();
+        assert_eq!(merged.collision, Collision::Top);
+        let mut velocity = Vec2::new(0.0, -400.0);
+        ball_reflection(&mut velocity, merged.collision);
+        assert_eq!(velocity, Vec2::new(0.0, 400.0));
+    }
+
+    #[test]
+    fn ball_sinking_into_flush_bricks_is_reflected_once() {
+        // overlapping both bricks by 3px across a seam with no gap
+        let bricks = bricks_with_gap(0.0);
+        let ball = ball_at(4.0, 27.0);
+
+        let contacts: Vec<Contact> = bricks.iter().filter_map(|brick| ball_collision(ball, *brick, false)).collect();
+        assert_eq!(contacts.len(), 2);
+
+        let merged = merge_contacts(&contacts, false).unwrap();
+        assert_eq!(merged.collision, Collision::Top);
+        assert!((merged.penetration - 3.0).abs() < 1e-5);
+
+        // reflecting once per contact would send the ball back down into the bricks
+        let mut velocity = Vec2::new(100.0, -400.0);
+        ball_reflection(&mut velocity, merged.collision);
+        assert_eq!(velocity, Vec2::new(100.0, 400.0));
+    }
+
+    #[test]
+    fn ball_in_an_inside_corner_bounces_off_both_faces() {
+        // two bricks' top faces and a wall's left face: the repeated normal must not outweigh the other
+        let face = |normal: Vec2| Contact { normal, penetration: 1.0, point: Vec2::ZERO, collision: Collision::from_normal(normal, false) };
+        let contacts = [face(Vec2::Y), face(Vec2::Y), face(Vec2::NEG_X)];
+
+        let merged = merge_contacts(&contacts, false).unwrap();
+        assert!(merged.normal.abs_diff_eq(Vec2::new(-1.0, 1.0).normalize(), 1e-5));
+        let mut velocity = Vec2::new(300.0, -400.0);
+        ball_reflection(&mut velocity, merged.collision);
+        assert!(velocity.x < 0.0 && velocity.y > 0.0, "still moving into a face: {velocity}");
+    }
+
+    #[test]
+    fn opposite_contacts_fall_back_to_the_first_normal() {
+        let walls = [
+            Aabb2d::new(Vec2::new(-20.0, 0.0), Vec2::new(5.0, 100.0)),
+            Aabb2d::new(Vec2::new(20.0, 0.0), Vec2::new(5.0, 100.0)),
+        ];
+        let contacts: Vec<Contact> = walls.iter().filter_map(|wall| ball_collision(ball_at(0.0, 0.0), *wall, false)).collect();
+
+        let merged = merge_contacts(&contacts, false).unwrap();
+        assert_eq!(merged.normal, contacts[0].normal);
+        assert!(merge_contacts(&[], false).is_none());
+    }
+}
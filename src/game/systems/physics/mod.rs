@@ -1,3 +1,5 @@
+pub mod bricks;
 pub mod collisions;
 pub mod paddle;
-pub mod time;
\ No newline at end of file
+pub mod serve;
+pub mod time;
@@ -1,27 +1,69 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, window::PrimaryWindow};
 use crate::game::prelude::*;
 
+pub fn read_paddle_input(
+    actions: Res<ActionState>,
+    mut paddle_input: ResMut<PaddleInput>,
+) {
+    // buttons move at full speed, a stick in proportion to how far it is pushed
+    paddle_input.direction = actions.value(Action::MoveRight) - actions.value(Action::MoveLeft);
+    // kept until a physics tick consumes it
+    paddle_input.serve |= actions.just_pressed(Action::Serve);
+}
+
+pub fn read_pointer_input(
+    touches: Res<Touches>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera2d>>,
+    config: Res<GameConfig>,
+    mut paddle_input: ResMut<PaddleInput>,
+) {
+    if config.paddle_control != PaddleControl::Pointer {
+        paddle_input.target_x = None;
+        return;
+    }
+
+    // a finger on the screen wins over the cursor
+    let (camera, camera_transform) = *camera;
+    let pointer = touches.first_pressed_position()
+        .or_else(|| window.single().ok().and_then(Window::cursor_position));
+    paddle_input.target_x = pointer
+        .and_then(|position| camera.viewport_to_world_2d(camera_transform, position).ok())
+        .map(|world_position| world_position.x);
+
+    // clicks serve through the `Serve` binding
+    paddle_input.serve |= touches.any_just_pressed();
+}
+
 pub fn move_paddle(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut paddle_transform: Single<&mut Transform, With<Paddle>>,
+    paddle_input: Res<PaddleInput>,
+    paddle_query: Single<(&mut Transform, &mut PaddleMotion), With<Paddle>>,
+    config: Res<GameConfig>,
     time: Res<Time>,
-    game_state: Res<GameState>,
 ) {
-    let mut direction = 0.0;
-
-    if *game_state != GameState::Playing { return; }
+    let (mut paddle_transform, mut paddle_motion) = paddle_query.into_inner();
 
-    if keyboard_input.pressed(KeyCode::ArrowLeft) {
-        direction -= 1.0;
-    }
-    if keyboard_input.pressed(KeyCode::ArrowRight) {
-        direction += 1.0;
-    }
+    let new_paddle_position = match paddle_input.target_x {
+        // follows the pointer, but no faster than `pointer_max_speed`
+        Some(target_x) => {
+            let max_step = config.pointer_max_speed * time.delta_secs();
+            paddle_transform.translation.x + (target_x - paddle_transform.translation.x).clamp(-max_step, max_step)
+        }
+        None => paddle_transform.translation.x + paddle_input.direction * config.paddle_speed * time.delta_secs(),
+    };
 
-    let new_paddle_position = paddle_transform.translation.x + direction * PADDLE_SPEED * time.delta_secs();
+    // the width changes with the wide paddle power-up
+    let half_width = paddle_transform.scale.x / 2.0;
+    let left_bound = config.left_wall + config.wall_thickness / 2.0 + half_width + config.paddle_padding;
+    let right_bound = config.right_wall - config.wall_thickness / 2.0 - half_width - config.paddle_padding;
 
-    let left_bound = LEFT_WALL + WALL_THICKNESS / 2.0 + PADDLE_SIZE.x / 2.0 + PADDLE_PADDING;
-    let right_bound = RIGHT_WALL - WALL_THICKNESS / 2.0 - PADDLE_SIZE.x / 2.0 - PADDLE_PADDING;
+    let old_paddle_position = paddle_transform.translation.x;
+    paddle_transform.translation.x = new_paddle_position.clamp(left_bound, right_bound);
 
-    paddle_transform.translation.x = new_paddle_position.clamp(left_bound, right_bound)
+    // actual motion after clamping, so a paddle pushed into a wall counts as still
+    **paddle_motion = if time.delta_secs() > 0.0 {
+        (paddle_transform.translation.x - old_paddle_position) / time.delta_secs()
+    } else {
+        0.0
+    };
 }
\ No newline at end of file
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use crate::game::prelude::*;
+
+pub fn ball_rest_position(paddle_translation: Vec3, config: &GameConfig) -> Vec3 {
+    Vec3::new(
+        paddle_translation.x,
+        paddle_translation.y + config.paddle_size.y / 2.0 + config.ball_diameter / 2.0 + GAP_BETWEEN_BALL_AND_PADDLE,
+        BALL_Z,
+    )
+}
+
+// Moving right tilts the serve to the right and vice versa; a still paddle serves straight up.
+pub fn serve_direction(paddle_motion: f32, paddle_speed: f32) -> Vec2 {
+    let angle = (paddle_motion / paddle_speed).clamp(-1.0, 1.0) * SERVE_MAX_ANGLE;
+    Vec2::new(angle.sin(), angle.cos())
+}
+
+pub fn carry_served_balls(
+    paddle_transform: Single<&Transform, (With<Paddle>, Without<Ball>)>,
+    mut ball_query: Query<&mut Transform, (With<Ball>, With<OnPaddle>)>,
+    config: Res<GameConfig>,
+) {
+    for mut ball_transform in &mut ball_query {
+        ball_transform.translation = ball_rest_position(paddle_transform.translation, &config);
+    }
+}
+
+pub fn serve_ball(
+    mut commands: Commands,
+    mut paddle_input: ResMut<PaddleInput>,
+    paddle_motion: Single<&PaddleMotion, With<Paddle>>,
+    active_power_ups: Res<ActivePowerUps>,
+    config: Res<GameConfig>,
+    mut ball_query: Query<(Entity, &mut Velocity), (With<Ball>, With<OnPaddle>, Without<RespawnCountdown>)>,
+) {
+    // a press while no ball is waiting is dropped rather than saved for later
+    if !std::mem::take(&mut paddle_input.serve) { return; }
+
+    for (entity, mut velocity) in &mut ball_query {
+        **velocity = serve_direction(***paddle_motion, config.paddle_speed) * active_power_ups.ball_speed(&config);
+        commands.entity(entity).remove::<OnPaddle>();
+    }
+}
@@ -1,17 +1,54 @@
 use bevy::prelude::*;
 use crate::game::prelude::*;
 
-pub fn apply_velocity(mut query: Query<(&mut Transform, &Velocity)>, time: Res<Time>, game_state: Res<GameState>) {
-    if *game_state != GameState::Playing { return; }
+// balls are moved by `check_for_collisions`, which sweeps them against the colliders
+pub fn apply_velocity(mut query: Query<(&mut Transform, &Velocity), Without<Ball>>, time: Res<Time>) {
     for (mut transform, velocity) in &mut query {
         transform.translation.x += velocity.x * time.delta_secs();
         transform.translation.y += velocity.y * time.delta_secs();
     }
 }
 
-pub fn tick_paddle_cooldowns(time: Res<Time>, mut query: Query<&mut PaddleCooldown, With<Ball>>) {
-    let dt = time.delta_secs();
+pub fn tick_paddle_cooldowns(mut query: Query<&mut PaddleCooldown, With<Ball>>) {
     for mut cooldown in &mut query {
-        cooldown.0 = (cooldown.0 - dt).max(0.0);
+        cooldown.0 = cooldown.0.saturating_sub(1);
+    }
+}
+
+pub fn tick_respawn_countdowns(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut RespawnCountdown, &mut Visibility), With<Ball>>,
+) {
+    for (entity, mut countdown, mut visibility) in &mut query {
+        if countdown.tick(time.delta()).just_finished() {
+            // back on the paddle, ready to be served
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<RespawnCountdown>();
+        }
+    }
+}
+
+pub fn store_previous_translations(mut query: Query<(&Transform, &mut Interpolated)>) {
+    for (transform, mut interpolated) in &mut query {
+        interpolated.previous = transform.translation;
+    }
+}
+
+// undo the blending of the last frame so the physics ticks continue from the real positions
+pub fn restore_physics_translations(mut query: Query<(&mut Transform, &Interpolated)>) {
+    for (mut transform, interpolated) in &mut query {
+        transform.translation = interpolated.current;
+    }
+}
+
+pub fn interpolate_translations(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &mut Interpolated)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+    for (mut transform, mut interpolated) in &mut query {
+        interpolated.current = transform.translation;
+        transform.translation = interpolated.previous.lerp(interpolated.current, alpha);
     }
 }
\ No newline at end of file
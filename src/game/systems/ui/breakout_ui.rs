@@ -1,42 +1,49 @@
 use bevy::prelude::*;
-use crate::game::prelude::*;
+use crate::game::{prelude::*, systems::physics::serve::ball_rest_position};
 
 pub fn spawn_breakout_ui(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    asset_server: Res<AssetServer>,
+    level: &Level,
+    config: &GameConfig,
 ) {
     // Note: Camera is spawned once in `setup_start_ui` to avoid multiple active cameras.
     // Do not spawn another Camera here.
 
-    // Sound
-    let ball_collision_sound = asset_server.load("sounds/breakout_collision.ogg");
-    commands.insert_resource(CollisionSound::new(ball_collision_sound));
-
     // Paddle
-    let paddle_y = BOTTOM_WALL + GAP_BETWEEN_PADDLE_AND_FLOOR;
+    let paddle_y = config.bottom_wall + config.gap_between_paddle_and_floor;
+
+    let paddle_translation = Vec3::new((config.left_wall + config.right_wall) / 2.0, paddle_y, 0.0);
 
     commands.spawn((
-        Sprite::from_color(PADDLE_COLOR, Vec2::ONE),
+        Sprite::from_color(config.paddle_color, Vec2::ONE),
         Transform {
-            translation: Vec3::new(0.0, paddle_y, 0.0),
-            scale: PADDLE_SIZE.extend(1.0),
+            translation: paddle_translation,
+            scale: config.paddle_size.extend(1.0),
             ..default()
         },
+        Interpolated::new(paddle_translation),
         Paddle,
+        PaddleMotion::default(),
         Collider,
+        DespawnOnExit(InGame),
     ));
 
-    // Ball
+    // Ball, resting on the paddle until it is served
+    let ball_translation = ball_rest_position(paddle_translation, config);
+
     commands.spawn((
         Mesh2d(meshes.add(Circle::default())),
-        MeshMaterial2d(materials.add(BALL_COLOR)),
-        Transform::from_translation(BALL_STARTING_POSITION)
-            .with_scale(Vec2::splat(BALL_DIAMETER).extend(1.0)),
+        MeshMaterial2d(materials.add(config.ball_color)),
+        Transform::from_translation(ball_translation)
+            .with_scale(Vec2::splat(config.ball_diameter).extend(1.0)),
+        Interpolated::new(ball_translation),
         Ball,
-        Velocity::new(INITIAL_BALL_DIRECTION.normalize() * BALL_SPEED),
-        PaddleCooldown(0.0),
+        Velocity::new(Vec2::ZERO),
+        PaddleCooldown(0),
+        OnPaddle,
+        DespawnOnExit(InGame),
     ));
 
     // Scoreboard
@@ -46,74 +53,101 @@ pub fn spawn_breakout_ui(
             font_size: SCOREBOARD_FONT_SIZE,
             ..default()
         },
-        TextColor(TEXT_COLOR),
+        TextColor(config.text_color),
         ScoreboardUi,
+        DespawnOnExit(InGame),
         Node {
             position_type: PositionType::Absolute,
             top: SCOREBOARD_TEXT_PADDING,
             left: SCOREBOARD_TEXT_PADDING,
             ..default()
         },
-        children![(
-            TextSpan::default(),
-            TextFont {
-                font_size: SCOREBOARD_FONT_SIZE,
-                ..default()
-            },
-            TextColor(SCORE_COLOR)
-        )],
+        children![
+            (
+                TextSpan::default(),
+                TextFont {
+                    font_size: SCOREBOARD_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(config.score_color)
+            ),
+            (
+                TextSpan::new("  Lives: "),
+                TextFont {
+                    font_size: SCOREBOARD_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(config.text_color)
+            ),
+            (
+                TextSpan::default(),
+                TextFont {
+                    font_size: SCOREBOARD_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(config.score_color)
+            ),
+            (
+                TextSpan::new("  Combo: "),
+                TextFont {
+                    font_size: SCOREBOARD_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(config.text_color)
+            ),
+            (
+                TextSpan::default(),
+                TextFont {
+                    font_size: SCOREBOARD_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(config.score_color)
+            ),
+        ],
+    ));
+
+    // Countdown shown while the ball waits to respawn; empty otherwise
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: COUNTDOWN_FONT_SIZE,
+            ..default()
+        },
+        TextColor(config.title_text_color),
+        CountdownUi,
+        DespawnOnExit(InGame),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(55.0),
+            left: Val::Percent(48.0),
+            ..default()
+        },
     ));
 
     // Walls
-    commands.spawn(Wall::new(WallLocation::Left));
-    commands.spawn(Wall::new(WallLocation::Right));
+    commands.spawn((Wall::new(WallLocation::Left, config), DespawnOnExit(InGame)));
+    commands.spawn((Wall::new(WallLocation::Right, config), DespawnOnExit(InGame)));
     // bottom wall: mark with BottomWall so we can detect game-over collisions
-    let (w, s, t) = Wall::new(WallLocation::Bottom);
-    commands.spawn((w, s, t, BottomWall));
-    commands.spawn(Wall::new(WallLocation::Top));
+    commands.spawn((Wall::new(WallLocation::Bottom, config), BottomWall, DespawnOnExit(InGame)));
+    commands.spawn((Wall::new(WallLocation::Top, config), DespawnOnExit(InGame)));
 
     // Bricks
-    let total_width_of_bricks = (RIGHT_WALL - LEFT_WALL) - 2.0 * GAP_BETWEEN_BRICKS_AND_SIDES;
-    let bottom_edge_of_bricks = paddle_y + GAP_BETWEEN_PADDLE_AND_BRICKS;
-    let total_height_of_bricks = TOP_WALL - bottom_edge_of_bricks - GAP_BETWEEN_BRICKS_AND_CEILING;
-
-    assert!(total_width_of_bricks > 0.0);
-    assert!(total_height_of_bricks > 0.0);
-
-    let n_columns = (total_width_of_bricks / (BRICK_SIZE.x + GAP_BETWEEN_BRICKS)).floor() as usize;
-    let n_rows = (total_height_of_bricks / (BRICK_SIZE.y + GAP_BETWEEN_BRICKS)).floor() as usize;
-    let n_vertical_gaps = n_columns - 1;
-
-    let center_of_bricks = (LEFT_WALL + RIGHT_WALL) / 2.0;
-    let left_edge_of_bricks = center_of_bricks
-        - (n_columns as f32 / 2.0 * BRICK_SIZE.x)
-        - n_vertical_gaps as f32 / 2.0 * GAP_BETWEEN_BRICKS;
-
-    let offset_x = left_edge_of_bricks + BRICK_SIZE.x / 2.0;
-    let offset_y = bottom_edge_of_bricks + BRICK_SIZE.y / 2.0;
-
-    for row in 0..n_rows {
-        for column in 0..n_columns {
-            let brick_position = Vec2::new(
-                offset_x + column as f32 * (BRICK_SIZE.x + GAP_BETWEEN_BRICKS),
-                offset_y + row as f32 * (BRICK_SIZE.y + GAP_BETWEEN_BRICKS),
-            );
-
-            // Bricks
-            commands.spawn((
-                Sprite {
-                    color: BRICK_COLOR,
-                    ..default()
-                },
-                Transform {
-                    translation: brick_position.extend(0.0),
-                    scale: Vec3::new(BRICK_SIZE.x, BRICK_SIZE.y, 1.0),
-                    ..default()
-                },
-                Brick,
-                Collider,
-            ));
-        }
+    for (brick_position, level_brick) in level.brick_positions(config) {
+        let brick = Brick::new(level_brick.kind);
+        commands.spawn((
+            Sprite {
+                color: brick.color(config),
+                ..default()
+            },
+            Transform {
+                translation: brick_position.extend(0.0),
+                scale: level.brick_size.extend(1.0),
+                ..default()
+            },
+            brick,
+            Collider,
+            DespawnOnExit(InGame),
+        ));
     }
 
 }
\ No newline at end of file
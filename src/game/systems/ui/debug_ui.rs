@@ -0,0 +1,52 @@
+use bevy::{diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin}, prelude::*};
+use crate::game::prelude::*;
+
+pub fn spawn_debug_readout(mut commands: Commands) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: DEBUG_FONT_SIZE,
+            ..default()
+        },
+        TextColor(DEBUG_COLLIDER_COLOR),
+        DebugReadout,
+        Visibility::Hidden,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: SCOREBOARD_TEXT_PADDING,
+            left: SCOREBOARD_TEXT_PADDING,
+            ..default()
+        },
+    ));
+}
+
+pub fn update_debug_readout(
+    overlay: Res<DebugOverlay>,
+    diagnostics: Res<DiagnosticsStore>,
+    entities: Query<Entity>,
+    balls: Query<&PaddleCooldown, With<Ball>>,
+    bricks: Query<(), With<Brick>>,
+    colliders: Query<(), With<Collider>>,
+    readout: Single<(&mut Text, &mut Visibility), With<DebugReadout>>,
+) {
+    let (mut text, mut visibility) = readout.into_inner();
+    *visibility = if overlay.enabled { Visibility::Visible } else { Visibility::Hidden };
+    if !overlay.enabled {
+        return;
+    }
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or(0.0);
+    let cooldowns = balls.iter().map(|cooldown| cooldown.0.to_string()).collect::<Vec<_>>().join(" ");
+    let last_contact = overlay.last_contact.map_or(String::from("none"), |contact| format!("{:?}", contact.collision));
+
+    text.0 = format!(
+        "FPS: {fps:.0}\nEntities: {}  Balls: {}  Bricks: {}  Colliders: {}\nPaddle cooldown: {cooldowns}\nLast contact: {last_contact}",
+        entities.iter().len(),
+        balls.iter().len(),
+        bricks.iter().len(),
+        colliders.iter().len(),
+    );
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use crate::game::prelude::*;
+
+pub fn spawn_high_score_table(commands: &mut Commands, high_scores: &HighScores, config: &GameConfig) {
+    let mut table = String::from("High Scores\n");
+    if high_scores.entries().is_empty() {
+        table.push_str("No scores yet\n");
+    }
+    for (rank, entry) in high_scores.entries().iter().enumerate() {
+        table.push_str(&format!(
+            "{:>2}. {:<width$} {:>6}  Stage {}  {}\n",
+            rank + 1,
+            entry.name,
+            entry.score,
+            entry.level,
+            entry.date,
+            width = HIGH_SCORE_NAME_MAX_LEN,
+        ));
+    }
+
+    commands.spawn((
+        Text::new(table),
+        TextFont {
+            font_size: HIGH_SCORE_FONT_SIZE,
+            ..default()
+        },
+        TextColor(config.text_color),
+        StartUi,
+        DespawnOnExit(GameState::Menu),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(5.0),
+            right: Val::Percent(5.0),
+            ..default()
+        },
+    ));
+}
+
+pub fn spawn_name_entry_ui(commands: &mut Commands, state: GameState, config: &GameConfig) {
+    commands.spawn((
+        Text::default(),
+        TextFont {
+            font_size: START_UI_TITLE_FONT_SIZE / 4.0,
+            ..default()
+        },
+        TextColor(config.score_color),
+        NameEntryUi,
+        DespawnOnExit(state),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(15.0),
+            left: Val::Percent(20.0),
+            ..default()
+        },
+    ));
+}
+
+pub fn update_name_entry_ui(
+    name_entry: Res<NameEntry>,
+    mut query: Query<&mut Text, With<NameEntryUi>>,
+) {
+    for mut text in &mut query {
+        text.0 = if name_entry.active {
+            format!("New High Score! Name: {}_  (Enter to save)", name_entry.name)
+        } else {
+            "Saved to the high score table".to_string()
+        };
+    }
+}
@@ -1,3 +1,21 @@
+use bevy::prelude::*;
+use crate::game::prelude::*;
+
 pub mod breakout_ui;
 pub mod start_ui;
-pub mod gameover_ui;
\ No newline at end of file
+pub mod gameover_ui;
+pub mod stageclear_ui;
+pub mod pause_ui;
+pub mod high_score_ui;
+pub mod settings_ui;
+pub mod debug_ui;
+
+// Dims the whole arena, walls included, behind a menu or message.
+pub fn overlay_backdrop(config: &GameConfig) -> (Sprite, Transform) {
+    let center = Vec2::new(config.left_wall + config.right_wall, config.bottom_wall + config.top_wall) / 2.0;
+    let size = Vec2::new(config.arena_width(), config.arena_height()) + Vec2::splat(200.0);
+    (
+        Sprite::from_color(Color::srgba(0.0, 0.0, 0.0, 0.5), size),
+        Transform::from_translation(center.extend(200.0)),
+    )
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use crate::game::{prelude::*, systems::ui::overlay_backdrop};
+
+pub fn spawn_pause_ui(
+    commands: &mut Commands,
+    config: &GameConfig,
+) {
+    commands.spawn((
+            overlay_backdrop(config),
+            DespawnOnExit(GameState::Paused),
+        ));
+
+    // title text
+    commands.spawn((
+        Text::new("Paused"),
+        TextFont {
+            font_size: START_UI_TITLE_FONT_SIZE,
+            ..default()
+        },
+        TextColor(config.title_text_color),
+        DespawnOnExit(GameState::Paused),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(25.0),
+            left: Val::Percent(20.0),
+            ..default()
+        },
+    ));
+
+    // one line per option, highlighted by `highlight_pause_selection`
+    for (i, option) in PauseMenuOption::ALL.iter().enumerate() {
+        commands.spawn((
+            Text::new(option.label()),
+            TextFont {
+                font_size: START_UI_TITLE_FONT_SIZE / 3.0,
+                ..default()
+            },
+            TextColor(config.text_color),
+            *option,
+            DespawnOnExit(GameState::Paused),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(45.0 + i as f32 * 8.0),
+                left: Val::Percent(22.0),
+                ..default()
+            },
+        ));
+    }
+}
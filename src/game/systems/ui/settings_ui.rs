@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use crate::game::{prelude::*, systems::ui::overlay_backdrop};
+
+pub fn spawn_settings_ui(
+    commands: &mut Commands,
+    config: &GameConfig,
+) {
+    commands.spawn((
+            overlay_backdrop(config),
+            DespawnOnExit(GameState::Settings),
+        ));
+
+    // title text
+    commands.spawn((
+        Text::new("Controls"),
+        TextFont {
+            font_size: START_UI_TITLE_FONT_SIZE / 2.0,
+            ..default()
+        },
+        TextColor(config.title_text_color),
+        DespawnOnExit(GameState::Settings),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(8.0),
+            left: Val::Percent(20.0),
+            ..default()
+        },
+    ));
+
+    // one line per action and one for the paddle control, filled in by `update_settings_ui`
+    for i in 0..SettingsRow::COUNT {
+        commands.spawn((
+            Text::default(),
+            TextFont {
+                font_size: HIGH_SCORE_FONT_SIZE,
+                ..default()
+            },
+            TextColor(config.text_color),
+            SettingsRow::at(i),
+            DespawnOnExit(GameState::Settings),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(22.0 + i as f32 * 5.5),
+                left: Val::Percent(20.0),
+                ..default()
+            },
+        ));
+    }
+
+    commands.spawn((
+        Text::new("Confirm on an action, then press the input to bind to it.\nConfirm on Paddle to switch between buttons and the mouse. Back returns to the menu."),
+        TextFont {
+            font_size: HIGH_SCORE_FONT_SIZE,
+            ..default()
+        },
+        TextColor(config.text_color),
+        DespawnOnExit(GameState::Settings),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(8.0),
+            left: Val::Percent(20.0),
+            ..default()
+        },
+    ));
+}
+
+pub fn update_settings_ui(
+    selection: Res<SettingsSelection>,
+    bindings: Res<InputBindings>,
+    mut rows: Query<(&SettingsRow, &mut Text, &mut TextColor)>,
+    config: Res<GameConfig>,
+) {
+    for (row, mut text, mut color) in &mut rows {
+        let selected = SettingsRow::at(selection.row) == *row;
+        text.0 = match row {
+            SettingsRow::Binding(action) => {
+                let inputs = if selected && selection.listening {
+                    "press an input...".to_string()
+                } else {
+                    bindings.get(*action).iter().map(Binding::label).collect::<Vec<_>>().join(", ")
+                };
+                format!("{:<12} {inputs}", action.label())
+            }
+            SettingsRow::PaddleControl => format!("{:<12} {}", "Paddle", config.paddle_control.label()),
+        };
+        color.0 = if selected { config.title_text_color } else { config.text_color };
+    }
+}
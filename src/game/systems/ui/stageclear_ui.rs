@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use crate::game::{prelude::*, systems::ui::overlay_backdrop};
+
+pub fn spawn_stageclear_ui(
+    mut commands: Commands,
+    score: Res<Score>,
+    current_level: Res<CurrentLevel>,
+    campaigns: Res<Assets<Campaign>>,
+    config: Res<GameConfig>,
+    bindings: Res<InputBindings>,
+) {
+    let confirm = bindings.first_label(Action::Confirm);
+
+    commands.spawn((
+            overlay_backdrop(&config),
+            StartUi,
+            DespawnOnExit(GameState::StageClear),
+        ));
+
+    // the final stage shows the victory screen instead
+    let (title, prompt) = if current_level.is_last(&campaigns) {
+        info!("Campaign cleared! Final score: {}", score.get());
+        (format!("Victory!\nFinal Score: {}\n", score.get()), format!("Press {confirm} to Return to Menu"))
+    } else {
+        info!("Stage {} cleared!", current_level.index() + 1);
+        (format!("Stage {} Clear!\nScore: {}\n", current_level.index() + 1, score.get()), format!("Press {confirm} to Continue"))
+    };
+
+    commands.spawn((
+        Text::new(title),
+        TextFont {
+            font_size: START_UI_TITLE_FONT_SIZE,
+            ..default()
+        },
+        TextColor(config.title_text_color),
+        StartUi,
+        DespawnOnExit(GameState::StageClear),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(30.0),
+            left: Val::Percent(20.0),
+            ..default()
+        },
+        children![(
+            TextSpan::new(prompt),
+            TextFont {
+                font_size: START_UI_TITLE_FONT_SIZE / 4.0,
+                ..default()
+            },
+            TextColor(config.title_text_color),
+        )]
+    ));
+}
+
+// score carries over; the next level is spawned when `InGame` is entered again
+pub fn advance_level(mut current_level: ResMut<CurrentLevel>) {
+    current_level.advance();
+}
@@ -1,13 +1,23 @@
 use bevy::prelude::*;
-use crate::game::prelude::*;
+use crate::game::{prelude::*, systems::ui::{high_score_ui, overlay_backdrop}};
 
 pub fn spawn_start_ui(
     commands: &mut Commands,
+    high_scores: &HighScores,
+    bindings: &InputBindings,
+    config: &GameConfig,
 ) {
+    let prompt = format!(
+        "Press {} to Start\nPress {} for Controls\nPress {} to Watch the Last Replay",
+        bindings.first_label(Action::Confirm),
+        bindings.first_label(Action::Controls),
+        bindings.first_label(Action::WatchReplay),
+    );
+
     commands.spawn((
-            Sprite::from_color(Color::srgba(0.0, 0.0, 0.0, 0.5), Vec2::new(RIGHT_WALL - LEFT_WALL + 200.0, TOP_WALL - BOTTOM_WALL + 200.0)),
-            Transform::from_translation(Vec3::new(0.0, 0.0, 200.0)),
+            overlay_backdrop(config),
             StartUi,
+            DespawnOnExit(GameState::Menu),
         ));
 
     // title text
@@ -17,8 +27,9 @@ pub fn spawn_start_ui(
             font_size: START_UI_TITLE_FONT_SIZE,
             ..default()
         },
-        TextColor(TITLE_TEXT_COLOR),
+        TextColor(config.title_text_color),
         StartUi,
+        DespawnOnExit(GameState::Menu),
         Node {
             position_type: PositionType::Absolute,
             top: Val::Percent(40.0),
@@ -26,12 +37,34 @@ pub fn spawn_start_ui(
             ..default()
         },
         children![(
-            TextSpan::new("Press Space to Start"),
+            TextSpan::new(prompt),
             TextFont {
                 font_size: START_UI_TITLE_FONT_SIZE / 4.0,
                 ..default()
             },
-            TextColor(TITLE_TEXT_COLOR),
+            TextColor(config.title_text_color),
         )]
     ));
-}
\ No newline at end of file
+
+    high_score_ui::spawn_high_score_table(commands, high_scores, config);
+}
+
+pub fn spawn_load_error_ui(commands: &mut Commands, message: String, config: &GameConfig) {
+    commands.spawn((
+        Text::new(message),
+        TextFont {
+            font_size: HIGH_SCORE_FONT_SIZE,
+            ..default()
+        },
+        TextColor(config.title_text_color),
+        LoadErrorUi,
+        DespawnOnExit(GameState::Menu),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Percent(10.0),
+            left: Val::Percent(20.0),
+            right: Val::Percent(20.0),
+            ..default()
+        },
+    ));
+}
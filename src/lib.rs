@@ -0,0 +1,11 @@
+//! Breakout on Bevy. Add [`GamePlugin`] next to `DefaultPlugins`, or next to `MinimalPlugins`
+//! and [`plugins::headless::HeadlessPlugin`] to run without a window or audio device.
+
+// Bevy systems routinely take many parameters and nested query filters.
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
+
+mod game;
+
+pub use game::{GamePlugin, plugins, prelude};
+pub use game::core::components;
+pub use game::systems::physics::collisions;
@@ -1,11 +1,29 @@
-use bevy::prelude::*;
+use std::path::PathBuf;
 
-mod game;
-use game::GamePlugin;
+use bevy::prelude::*;
+use kuzusi::{prelude::GameConfig, GamePlugin};
 
+// `--replay <path>` plays a recording back; every other flag is a setting for `GameConfig::from_args`.
 fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let replay = take_replay_arg(&mut args);
+
+    let mut game = GamePlugin::default().with_config(GameConfig::from_args(args));
+    if let Some(path) = replay {
+        game = game.with_replay(path);
+    }
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(GamePlugin)
+        .add_plugins(game)
         .run();
-}
\ No newline at end of file
+}
+
+// Removes `--replay <path>` or `--replay=<path>` from `args`.
+fn take_replay_arg(args: &mut Vec<String>) -> Option<PathBuf> {
+    let index = args.iter().position(|arg| arg == "--replay" || arg.starts_with("--replay="))?;
+    let arg = args.remove(index);
+    match arg.strip_prefix("--replay=") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => (index < args.len()).then(|| PathBuf::from(args.remove(index))),
+    }
+}
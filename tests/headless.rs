@@ -0,0 +1,445 @@
+//! Runs the whole game headless, one physics tick per `App::update`, driving it with key messages.
+
+use std::{path::PathBuf, time::Duration};
+
+use bevy::{
+    input::{
+        gamepad::{GamepadConnection, GamepadConnectionEvent, RawGamepadButtonChangedEvent, RawGamepadEvent},
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+    time::TimeUpdateStrategy,
+};
+use kuzusi::{plugins::headless::HeadlessPlugin, prelude::*, GamePlugin};
+
+const MAX_LOADING_FRAMES: usize = 1000;
+
+// a directory of its own for each test, since tests run in parallel and every stage saves a replay
+fn temp_dir(test: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("kuzusi-{}-{test}", std::process::id()))
+}
+
+fn headless_app(test: &str, config: GameConfig) -> App {
+    let dir = temp_dir(test);
+    let tick = Duration::from_secs_f64(1.0 / config.physics_tick_rate);
+    let mut app = App::new();
+    app
+        // never read or write the player's own table and controls
+        .insert_resource(HighScoreFile(dir.join("highscores.ron")))
+        .insert_resource(InputBindingsFile(dir.join("bindings.ron")))
+        .insert_resource(ReplayFile(dir.join("replay.ron")))
+        .add_plugins((MinimalPlugins, HeadlessPlugin, GamePlugin::default().with_config(config)))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(tick));
+    app
+}
+
+// Loads the campaign, then enters the first stage with the ball resting on the paddle.
+fn start_game(test: &str, config: GameConfig) -> App {
+    let mut app = load_campaign(test, config);
+    tap(&mut app, KeyCode::Enter);
+    assert_eq!(state(&app), GameState::Playing, "campaign did not load");
+    app
+}
+
+// Waits on the menu until every stage has loaded.
+fn load_campaign(test: &str, config: GameConfig) -> App {
+    let mut app = headless_app(test, config);
+    for _ in 0..MAX_LOADING_FRAMES {
+        app.update();
+        let world = app.world();
+        let campaign = world.resource::<CurrentLevel>().campaign().clone();
+        if world.resource::<AssetServer>().is_loaded_with_dependencies(&campaign) {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    app
+}
+
+fn send_key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+    app.world_mut().write_message(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(bevy::input::keyboard::NativeKey::Unidentified),
+        state,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+fn hold(app: &mut App, key_code: KeyCode) {
+    send_key(app, key_code, ButtonState::Pressed);
+}
+
+fn release(app: &mut App, key_code: KeyCode) {
+    send_key(app, key_code, ButtonState::Released);
+}
+
+// press and release over one frame each
+const TAP_FRAMES: usize = 2;
+
+fn tap(app: &mut App, key_code: KeyCode) {
+    hold(app, key_code);
+    app.update();
+    release(app, key_code);
+    app.update();
+}
+
+// a controller as the gamepad backend would announce it
+fn connect_gamepad(app: &mut App) -> Entity {
+    let gamepad = app.world_mut().spawn_empty().id();
+    let connection = GamepadConnection::Connected { name: "Test Pad".to_string(), vendor_id: None, product_id: None };
+    app.world_mut().write_message(GamepadConnectionEvent::new(gamepad, connection));
+    app.update();
+    gamepad
+}
+
+fn tap_button(app: &mut App, gamepad: Entity, button: GamepadButton) {
+    app.world_mut().write_message(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(gamepad, button, 1.0)));
+    app.update();
+    app.world_mut().write_message(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(gamepad, button, 0.0)));
+    app.update();
+}
+
+fn step(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+// steps until `done` holds, giving up after `max_frames`
+fn step_until(app: &mut App, max_frames: usize, done: impl Fn(&App) -> bool) -> bool {
+    frames_until(app, max_frames, done).is_some()
+}
+
+// like `step_until`, returning how many frames it took
+fn frames_until(app: &mut App, max_frames: usize, done: impl Fn(&App) -> bool) -> Option<usize> {
+    for frame in 0..max_frames {
+        if done(app) {
+            return Some(frame);
+        }
+        app.update();
+    }
+    done(app).then_some(max_frames)
+}
+
+fn state(app: &App) -> GameState {
+    *app.world().resource::<State<GameState>>().get()
+}
+
+fn score(app: &App) -> usize {
+    app.world().resource::<Score>().get()
+}
+
+fn brick_count(app: &mut App) -> usize {
+    app.world_mut().query_filtered::<(), With<Brick>>().iter(app.world()).count()
+}
+
+fn ball_position(app: &mut App) -> Vec2 {
+    app.world_mut().query_filtered::<&Interpolated, With<Ball>>().single(app.world()).unwrap().current.truncate()
+}
+
+fn paddle_position(app: &mut App) -> Vec2 {
+    app.world_mut().query_filtered::<&Interpolated, With<Paddle>>().single(app.world()).unwrap().current.truncate()
+}
+
+#[test]
+fn starting_the_game_spawns_the_first_stage() {
+    let mut app = start_game("starting_the_game_spawns_the_first_stage", GameConfig::default());
+
+    // stage 1 is a full 8 x 7 grid
+    assert_eq!(brick_count(&mut app), 56);
+    assert_eq!(app.world().resource::<RemainingBricks>().get(), 56);
+    assert_eq!(score(&app), 0);
+    let ball = ball_position(&mut app);
+    let paddle = paddle_position(&mut app);
+    assert_eq!(ball.x, paddle.x);
+    assert!(ball.y > paddle.y);
+}
+
+#[test]
+fn a_campaign_that_fails_to_load_is_reported_on_the_menu() {
+    let mut app = headless_app("a_campaign_that_fails_to_load_is_reported_on_the_menu", GameConfig::default());
+    let campaign = app.world().resource::<AssetServer>().load::<Campaign>("levels/missing.campaign.ron");
+    app.insert_resource(CurrentLevel::new(campaign.clone()));
+
+    let failed = step_until(&mut app, MAX_LOADING_FRAMES, |app| {
+        std::thread::sleep(Duration::from_millis(1));
+        app.world().resource::<AssetServer>().load_state(&campaign).is_failed()
+    });
+    assert!(failed);
+    tap(&mut app, KeyCode::Enter);
+
+    let error_ui = app.world_mut().query_filtered::<(), With<LoadErrorUi>>().iter(app.world()).count();
+    assert_eq!(error_ui, 1);
+    assert_eq!(state(&app), GameState::Menu);
+}
+
+#[test]
+fn held_arrow_moves_the_paddle_and_the_resting_ball() {
+    let mut app = start_game("held_arrow_moves_the_paddle_and_the_resting_ball", GameConfig::default());
+    let start = paddle_position(&mut app);
+
+    hold(&mut app, KeyCode::ArrowLeft);
+    step(&mut app, 60);
+    release(&mut app, KeyCode::ArrowLeft);
+    step(&mut app, 1);
+
+    let paddle = paddle_position(&mut app);
+    let travelled = start.x - paddle.x;
+    // half a second at the default paddle speed
+    assert!((travelled - 250.0).abs() < 10.0, "paddle moved {travelled}");
+    assert_eq!(ball_position(&mut app).x, paddle.x);
+}
+
+#[test]
+fn served_ball_rises_from_a_still_paddle() {
+    let mut app = start_game("served_ball_rises_from_a_still_paddle", GameConfig::default());
+    let start = ball_position(&mut app);
+
+    tap(&mut app, KeyCode::Space);
+    step(&mut app, 30);
+
+    let ball = ball_position(&mut app);
+    assert!(ball.y > start.y + 50.0);
+    assert!((ball.x - start.x).abs() < 1e-3);
+}
+
+#[test]
+fn rebound_serve_key_replaces_the_old_one() {
+    let mut app = start_game("rebound_serve_key_replaces_the_old_one", GameConfig::default());
+    app.world_mut().resource_mut::<InputBindings>().rebind(Action::Serve, Binding::Key(KeyCode::KeyW));
+    let start = ball_position(&mut app);
+
+    tap(&mut app, KeyCode::Space);
+    step(&mut app, 30);
+    assert_eq!(ball_position(&mut app), start);
+
+    tap(&mut app, KeyCode::KeyW);
+    step(&mut app, 30);
+    assert!(ball_position(&mut app).y > start.y + 50.0);
+}
+
+#[test]
+fn controls_screen_switches_the_paddle_to_the_pointer() {
+    let mut app = load_campaign("controls_screen_switches_the_paddle_to_the_pointer", GameConfig::default());
+
+    tap(&mut app, KeyCode::KeyC);
+    assert_eq!(state(&app), GameState::Settings);
+    // the paddle row comes last, one up from the first
+    tap(&mut app, KeyCode::ArrowUp);
+    tap(&mut app, KeyCode::Enter);
+
+    assert_eq!(app.world().resource::<GameConfig>().paddle_control, PaddleControl::Pointer);
+    assert_eq!(state(&app), GameState::Settings);
+}
+
+#[test]
+fn served_ball_breaks_bricks_and_scores() {
+    let mut app = start_game("served_ball_breaks_bricks_and_scores", GameConfig::default());
+
+    tap(&mut app, KeyCode::Space);
+    assert!(step_until(&mut app, 240, |app| score(app) > 0), "no brick was hit");
+
+    assert!(brick_count(&mut app) < 56);
+    // stage 1 has no steel, so every brick left still counts
+    assert_eq!(app.world().resource::<RemainingBricks>().get(), brick_count(&mut app));
+    assert_eq!(state(&app), GameState::Playing);
+}
+
+#[test]
+fn exploding_brick_sets_off_its_neighbour_but_not_steel() {
+    let mut app = headless_app("exploding_brick_sets_off_its_neighbour_but_not_steel", GameConfig::default());
+    // five columns, so the straight serve meets the explosive brick in the middle one
+    let kinds = [(0, 1, BrickKind::Steel), (0, 2, BrickKind::Explosive), (0, 3, BrickKind::Explosive), (1, 0, BrickKind::Normal)];
+    let level = Level {
+        name: String::from("Explosions"),
+        brick_size: Vec2::new(100.0, 30.0),
+        gap: 5.0,
+        columns: 5,
+        bricks: kinds.iter().map(|&(row, column, kind)| LevelBrick { row, column, kind }).collect(),
+    };
+    let level = app.world_mut().resource_mut::<Assets<Level>>().add(level);
+    let campaign = app.world_mut().resource_mut::<Assets<Campaign>>().add(Campaign { levels: vec![level] });
+    app.insert_resource(CurrentLevel::new(campaign));
+    app.update();
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Playing);
+    step(&mut app, 1);
+    assert_eq!(brick_count(&mut app), 4);
+
+    tap(&mut app, KeyCode::Space);
+    assert!(step_until(&mut app, 240, |app| score(app) > 0), "no brick was hit");
+    step(&mut app, 1);
+
+    // both explosive bricks score at the first hit's multiplier of 1
+    assert_eq!(score(&app), 2 * 3);
+    let mut left = app.world_mut().query::<&Brick>().iter(app.world()).map(|brick| brick.kind).collect::<Vec<_>>();
+    left.sort_by_key(|kind| *kind == BrickKind::Normal);
+    assert_eq!(left, [BrickKind::Steel, BrickKind::Normal]);
+    assert_eq!(app.world().resource::<RemainingBricks>().get(), 1);
+}
+
+#[test]
+fn still_paddle_keeps_returning_a_straight_ball() {
+    let mut app = start_game("still_paddle_keeps_returning_a_straight_ball", GameConfig::default());
+    let lives = app.world().resource::<Lives>().get();
+
+    // a centre hit sends the ball straight back up, so it keeps landing on the same spot
+    tap(&mut app, KeyCode::Space);
+    step(&mut app, 1200);
+
+    assert_eq!(state(&app), GameState::Playing);
+    // power-ups may have added one
+    assert!(app.world().resource::<Lives>().get() >= lives, "the ball got past the paddle");
+    assert!(score(&app) > 0);
+}
+
+#[test]
+fn missing_the_ball_on_the_last_life_ends_the_game() {
+    let mut app = start_game("missing_the_ball_on_the_last_life_ends_the_game", GameConfig { starting_lives: 1, ..default() });
+
+    tap(&mut app, KeyCode::Space);
+    // get out of the way of the returning ball
+    hold(&mut app, KeyCode::ArrowLeft);
+
+    assert!(step_until(&mut app, 2000, |app| state(app) == GameState::GameOver), "the game did not end");
+}
+
+#[test]
+fn gamepad_confirm_saves_a_high_score_under_the_default_name() {
+    let mut app = start_game("gamepad_confirm_saves_a_high_score_under_the_default_name", GameConfig { starting_lives: 1, ..default() });
+    let gamepad = connect_gamepad(&mut app);
+
+    tap_button(&mut app, gamepad, GamepadButton::South);
+    assert!(step_until(&mut app, 240, |app| score(app) > 0), "no brick was hit");
+    hold(&mut app, KeyCode::ArrowLeft);
+    assert!(step_until(&mut app, 2000, |app| state(app) == GameState::GameOver), "the game did not end");
+    assert!(app.world().resource::<NameEntry>().active, "the score did not make the table");
+
+    tap_button(&mut app, gamepad, GamepadButton::South);
+    let high_scores = app.world().resource::<HighScores>();
+    assert_eq!(high_scores.entries().len(), 1);
+    assert_eq!(high_scores.entries()[0].name, "PLAYER");
+    assert_eq!(high_scores.entries()[0].score, score(&app));
+    // the press that saved stays on the overlay, the next one leaves it
+    assert_eq!(state(&app), GameState::GameOver);
+
+    tap_button(&mut app, gamepad, GamepadButton::South);
+    assert_eq!(state(&app), GameState::Menu);
+}
+
+#[test]
+fn restart_replays_the_current_stage_from_its_start() {
+    let mut app = load_campaign("restart_replays_the_current_stage_from_its_start", GameConfig::default());
+    // as if the first stage had been cleared with 300 points
+    app.world_mut().resource_mut::<CurrentLevel>().jump_to(1);
+    app.world_mut().resource_mut::<Score>().set(300);
+    tap(&mut app, KeyCode::Enter);
+    let bricks = brick_count(&mut app);
+
+    // serve at an angle from a moving paddle
+    hold(&mut app, KeyCode::ArrowRight);
+    step(&mut app, 20);
+    tap(&mut app, KeyCode::Space);
+    release(&mut app, KeyCode::ArrowRight);
+    assert!(step_until(&mut app, 600, |app| score(app) > 300), "no brick was hit");
+
+    tap(&mut app, KeyCode::Escape);
+    assert_eq!(state(&app), GameState::Paused);
+    tap(&mut app, KeyCode::ArrowDown);
+    tap(&mut app, KeyCode::Enter);
+
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(app.world().resource::<CurrentLevel>().index(), 1);
+    assert_eq!(score(&app), 300);
+    assert_eq!(brick_count(&mut app), bricks);
+}
+
+#[test]
+fn fast_ball_stays_inside_the_walls() {
+    // 50 units per tick against 10 unit thick walls
+    let config = GameConfig { ball_speed: 6000.0, ..default() };
+    let (left, right, top) = (config.left_wall, config.right_wall, config.top_wall);
+    let mut app = start_game("fast_ball_stays_inside_the_walls", config);
+
+    hold(&mut app, KeyCode::ArrowRight);
+    step(&mut app, 10);
+    tap(&mut app, KeyCode::Space);
+
+    for _ in 0..600 {
+        app.update();
+        if state(&app) != GameState::Playing {
+            break;
+        }
+        let ball = ball_position(&mut app);
+        assert!(ball.x > left && ball.x < right && ball.y < top, "ball escaped to {ball}");
+    }
+}
+
+#[test]
+fn replay_reproduces_the_recorded_stage() {
+    let mut app = start_game("replay_reproduces_the_recorded_stage", GameConfig { starting_lives: 1, ..default() });
+
+    // serve from a moving paddle, then get out of the way
+    hold(&mut app, KeyCode::ArrowRight);
+    step(&mut app, 20);
+    tap(&mut app, KeyCode::Space);
+    release(&mut app, KeyCode::ArrowRight);
+    hold(&mut app, KeyCode::ArrowLeft);
+    let rally_frames = frames_until(&mut app, 2000, |app| state(app) == GameState::GameOver).expect("the game did not end");
+    release(&mut app, KeyCode::ArrowLeft);
+    let recorded_score = score(&app);
+
+    // one tick per frame from entering the stage until it ended
+    let replay = Replay::load(&app.world().resource::<ReplayFile>().0).unwrap();
+    assert_eq!(replay.tick_count(), 20 + TAP_FRAMES + rally_frames);
+
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Menu);
+    step(&mut app, 1);
+    tap(&mut app, KeyCode::KeyR);
+    let played_frames = frames_until(&mut app, 2000, |app| state(app) == GameState::GameOver).expect("the replay did not end");
+
+    assert_eq!(played_frames, replay.tick_count());
+    assert_eq!(score(&app), recorded_score);
+}
+
+#[test]
+fn replay_runs_at_its_own_tick_rate() {
+    let mut app = load_campaign("replay_runs_at_its_own_tick_rate", GameConfig::default());
+    let player_step = app.world().resource::<Time<Fixed>>().timestep();
+    // a second of standing still, recorded by a player running the physics at 60 Hz
+    let mut replay = Replay::new(0, 1, 3, 0, GameConfig { physics_tick_rate: 60.0, ..default() });
+    for _ in 0..60 {
+        replay.push(TickInput::default());
+    }
+    replay.save(&app.world().resource::<ReplayFile>().0).unwrap();
+
+    tap(&mut app, KeyCode::KeyR);
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(app.world().resource::<Time<Fixed>>().timestep(), Duration::from_secs_f64(1.0 / 60.0));
+
+    // pausing and picking Restart stops the replay instead of restarting a run under it
+    tap(&mut app, KeyCode::Escape);
+    tap(&mut app, KeyCode::ArrowDown);
+    tap(&mut app, KeyCode::Enter);
+    step(&mut app, 1);
+    assert_eq!(state(&app), GameState::Menu);
+    assert!(app.world().get_resource::<ReplayPlayback>().is_none());
+    assert_eq!(app.world().resource::<Time<Fixed>>().timestep(), player_step);
+}
+
+#[test]
+fn quitting_mid_stage_keeps_the_recording() {
+    let mut app = start_game("quitting_mid_stage_keeps_the_recording", GameConfig::default());
+    tap(&mut app, KeyCode::Space);
+    step(&mut app, 30);
+
+    app.world_mut().write_message(AppExit::Success);
+    app.update();
+
+    let saved = Replay::load(&app.world().resource::<ReplayFile>().0).expect("nothing was saved");
+    let recording = &app.world().resource::<ReplayRecording>().0;
+    assert!(recording.tick_count() > 30);
+    assert_eq!(&saved, recording);
+}
@@ -0,0 +1,58 @@
+//! Embeds the game the way a launcher would, using nothing but the crate's public API.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use kuzusi::{plugins::{headless::HeadlessPlugin, physics::PhysicsPlugin}, prelude::*, GamePlugin};
+
+#[test]
+fn launcher_settings_reach_the_game() {
+    let dir = std::env::temp_dir().join(format!("kuzusi-{}-launcher", std::process::id()));
+    let config = GameConfig { ball_speed: 900.0, starting_lives: 5, ..default() };
+
+    let mut app = App::new();
+    app.insert_resource(HighScoreFile(dir.join("highscores.ron")))
+        .insert_resource(InputBindingsFile(dir.join("bindings.ron")))
+        .insert_resource(ReplayFile(dir.join("replay.ron")))
+        .add_plugins((MinimalPlugins, HeadlessPlugin, GamePlugin::default().with_config(config)))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(10)));
+    app.update();
+
+    // the launcher's settings win over the player's settings file
+    let config = app.world().resource::<GameConfig>();
+    assert_eq!(config.ball_speed, 900.0);
+    assert_eq!(config.starting_lives, 5);
+    assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Menu);
+    assert!(app.world().resource::<HighScores>().entries().is_empty());
+}
+
+#[test]
+fn physics_plugin_runs_on_its_own() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HeadlessPlugin, PhysicsPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(10)));
+    app.world_mut().spawn((Wall, Transform::from_xyz(100.0, 0.0, 0.0).with_scale(Vec3::new(20.0, 400.0, 1.0))));
+    // a brick out of the ball's way keeps the stage from counting as cleared
+    app.world_mut().spawn((
+        Brick::new(BrickKind::Normal),
+        Collider,
+        Sprite::default(),
+        Transform::from_xyz(-300.0, 300.0, 0.0).with_scale(Vec3::new(40.0, 20.0, 1.0)),
+    ));
+    let ball = app.world_mut().spawn((
+        Ball,
+        Velocity::new(Vec2::new(600.0, 0.0)),
+        PaddleCooldown(0),
+        Transform::from_scale(Vec3::splat(10.0)),
+    )).id();
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Playing);
+
+    for _ in 0..50 {
+        app.update();
+    }
+
+    // the ball came back off the wall
+    assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Playing);
+    assert!(app.world().get::<Velocity>(ball).unwrap().x < 0.0);
+    assert!(app.world().get::<Transform>(ball).unwrap().translation.x < 90.0);
+}